@@ -0,0 +1,166 @@
+use crate::{Color, Game, Move, State};
+use rand::{rng, seq::SliceRandom};
+use std::cmp::{Reverse, max, min};
+
+impl Game {
+    /// Searches four plies deep and returns the best move for colour `c`.
+    pub fn play_ai(&mut self, c: Color) -> Move {
+        let mut best_score = isize::MIN;
+        let mut best_mov: Option<Move> = None;
+
+        let mut moves: Vec<Move> = self.legal_moves().collect();
+        moves.shuffle(&mut rng());
+        self.order_moves(&mut moves);
+        let mut permutations = 0;
+        for mov in &moves {
+            let undo = self.apply(*mov);
+            let score = Game::minimax(self, 4, isize::MIN, isize::MAX, !c, c, &mut permutations);
+            self.unmake_move(undo);
+            if score > best_score {
+                best_score = score;
+                best_mov = Some(*mov);
+            }
+        }
+        best_mov.unwrap()
+    }
+
+    /// Sorts captures that win or trade material first, then quiet moves,
+    /// then captures that lose material according to [`Game::see`].
+    fn order_moves(&self, moves: &mut [Move]) {
+        moves.sort_by_cached_key(|&mov| {
+            if !mov.capture {
+                return Reverse((1, 0));
+            }
+            let see = self.see(mov);
+            Reverse((if see >= 0 { 2 } else { 0 }, see))
+        });
+    }
+
+    /// Whether to skip `mov` one ply above the leaves, where a capture that
+    /// loses the exchange would be scored before the recapture is seen.
+    fn prune(&self, mov: Move, depth: usize, searched: usize) -> bool {
+        depth == 1 && searched > 0 && mov.capture && self.see(mov) < 0
+    }
+
+    fn count_board(&self, c: Color) -> isize {
+        let mut score = 0;
+
+        let mut pieces = self.get_pieces(c);
+        pieces.append(&mut self.get_pieces(!c));
+
+        for square in pieces {
+            let piece = self.piece(square).unwrap();
+            score += piece.value() * (if piece.color == c { 1 } else { -1 })
+        }
+
+        score += match self.check_game_end() {
+            State::WhiteCheckmate => {
+                if c == Color::White {
+                    -10000
+                } else {
+                    10000
+                }
+            }
+            State::BlackCheckmate => {
+                if c == Color::Black {
+                    -10000
+                } else {
+                    10000
+                }
+            }
+            State::BlackStalemate
+            | State::WhiteStalemate
+            | State::DeadPosition
+            | State::ThreefoldRepetition
+            | State::FivefoldRepetition
+            | State::FiftyMoveRule
+            | State::SeventyFiveMoveRule => -15,
+            State::WhiteCheck => {
+                if c == Color::White {
+                    -20
+                } else {
+                    20
+                }
+            }
+            State::BlackCheck => {
+                if c == Color::Black {
+                    -20
+                } else {
+                    20
+                }
+            }
+            _ => 0,
+        };
+
+        score
+    }
+
+    fn minimax(
+        game: &mut Game,
+        depth: usize,
+        mut alpha: isize,
+        mut beta: isize,
+        c: Color,
+        maximising_player: Color,
+        permutations: &mut usize,
+    ) -> isize {
+        *permutations += 1;
+        if depth == 0 || game.is_game_over() {
+            return game.count_board(maximising_player);
+        }
+
+        let mut moves: Vec<Move> = game.legal_moves().collect();
+        moves.shuffle(&mut rng());
+        game.order_moves(&mut moves);
+
+        if c == maximising_player {
+            let mut max_eval = isize::MIN;
+            for (searched, mov) in moves.into_iter().enumerate() {
+                if game.prune(mov, depth, searched) {
+                    continue;
+                }
+                let undo = game.apply(mov);
+                let eval = Game::minimax(
+                    game,
+                    depth - 1,
+                    alpha,
+                    beta,
+                    !c,
+                    maximising_player,
+                    permutations,
+                );
+                game.unmake_move(undo);
+                max_eval = max(max_eval, eval);
+                alpha = max(alpha, eval);
+                if beta <= alpha {
+                    break;
+                }
+            }
+            max_eval
+        } else {
+            let mut min_eval = isize::MAX;
+            for (searched, mov) in moves.into_iter().enumerate() {
+                if game.prune(mov, depth, searched) {
+                    continue;
+                }
+                let undo = game.apply(mov);
+                let eval = Game::minimax(
+                    game,
+                    depth - 1,
+                    alpha,
+                    beta,
+                    !c,
+                    maximising_player,
+                    permutations,
+                );
+                game.unmake_move(undo);
+                min_eval = min(min_eval, eval);
+                beta = min(beta, eval);
+                if beta <= alpha {
+                    break;
+                }
+            }
+            min_eval
+        }
+    }
+}
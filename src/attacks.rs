@@ -0,0 +1,35 @@
+use crate::bitboard::{self, BISHOP, Bitboard, KING, KNIGHT, PAWN, QUEEN, ROOK};
+use crate::{Color, Game, Square};
+
+impl Game {
+    /// Squares holding a piece of colour `c` that attacks `square`, whether
+    /// or not that piece could legally capture there.
+    pub fn attackers_of(&self, square: Square, c: Color) -> Bitboard {
+        self.bitboards.attackers(square, c)
+    }
+
+    /// Every square attacked by at least one piece of colour `c`, including
+    /// squares holding its own pieces.
+    pub fn attack_map(&self, c: Color) -> Bitboard {
+        let pieces = &self.bitboards.pieces[c.index()];
+        let occupied = self.bitboards.occupied;
+
+        let mut attacks = 0;
+        for sq in bitboard::squares(pieces[PAWN]) {
+            attacks |= bitboard::PAWN_ATTACKS[c.index()][sq.index()];
+        }
+        for sq in bitboard::squares(pieces[KNIGHT]) {
+            attacks |= bitboard::KNIGHT_ATTACKS[sq.index()];
+        }
+        for sq in bitboard::squares(pieces[BISHOP] | pieces[QUEEN]) {
+            attacks |= bitboard::bishop_attacks(sq, occupied);
+        }
+        for sq in bitboard::squares(pieces[ROOK] | pieces[QUEEN]) {
+            attacks |= bitboard::rook_attacks(sq, occupied);
+        }
+        for sq in bitboard::squares(pieces[KING]) {
+            attacks |= bitboard::KING_ATTACKS[sq.index()];
+        }
+        attacks
+    }
+}
@@ -0,0 +1,203 @@
+use crate::magic;
+use crate::{Color, Piece, PieceKind, Square};
+
+/// A set of squares, one bit per square with bit `rank * 8 + file`.
+pub type Bitboard = u64;
+
+/// The bit for `square`.
+pub const fn bit(square: Square) -> Bitboard {
+    1 << square.index()
+}
+
+/// The lowest square in a non-empty bitboard.
+pub const fn first(bb: Bitboard) -> Square {
+    Square::from_bit(bb.trailing_zeros())
+}
+
+/// Iterates over the squares in a bitboard from a1 to h8.
+pub fn squares(mut bb: Bitboard) -> impl Iterator<Item = Square> {
+    std::iter::from_fn(move || {
+        if bb == 0 {
+            return None;
+        }
+        let square = first(bb);
+        bb &= bb - 1;
+        Some(square)
+    })
+}
+
+const fn leaper_attacks(offsets: &[(isize, isize)]) -> [Bitboard; 64] {
+    let mut table = [0; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let mut i = 0;
+        while i < offsets.len() {
+            let y = (sq / 8) as isize + offsets[i].0;
+            let x = (sq % 8) as isize + offsets[i].1;
+            if y >= 0 && y < 8 && x >= 0 && x < 8 {
+                table[sq] |= 1 << (y * 8 + x);
+            }
+            i += 1;
+        }
+        sq += 1;
+    }
+    table
+}
+
+/// Squares a knight attacks from each square.
+pub const KNIGHT_ATTACKS: [Bitboard; 64] = leaper_attacks(&[
+    (2, 1),
+    (2, -1),
+    (-2, 1),
+    (-2, -1),
+    (1, -2),
+    (-1, -2),
+    (1, 2),
+    (-1, 2),
+]);
+
+/// Squares a king attacks from each square.
+pub const KING_ATTACKS: [Bitboard; 64] = leaper_attacks(&[
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+]);
+
+/// Squares a pawn attacks from each square, indexed white then black.
+pub const PAWN_ATTACKS: [[Bitboard; 64]; 2] = [
+    leaper_attacks(&[(1, -1), (1, 1)]),
+    leaper_attacks(&[(-1, -1), (-1, 1)]),
+];
+
+pub(crate) const ROOK_DIRECTIONS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+pub(crate) const BISHOP_DIRECTIONS: [(isize, isize); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Walks each direction from `sq` until the edge or the first occupied
+/// square. Used to build the magic tables.
+pub(crate) fn sliding_attacks(
+    sq: usize,
+    occupied: Bitboard,
+    directions: &[(isize, isize)],
+) -> Bitboard {
+    let mut attacks = 0;
+    for (dy, dx) in directions {
+        let mut y = (sq / 8) as isize + dy;
+        let mut x = (sq % 8) as isize + dx;
+        while (0..8).contains(&y) && (0..8).contains(&x) {
+            let target = 1 << (y * 8 + x);
+            attacks |= target;
+            if occupied & target != 0 {
+                break;
+            }
+            y += dy;
+            x += dx;
+        }
+    }
+    attacks
+}
+
+/// Squares a rook on `sq` attacks given the occupied squares.
+pub fn rook_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    magic::ROOK.attacks(sq.index(), occupied)
+}
+
+/// Squares a bishop on `sq` attacks given the occupied squares.
+pub fn bishop_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    magic::BISHOP.attacks(sq.index(), occupied)
+}
+
+/// Squares a queen on `sq` attacks given the occupied squares.
+pub fn queen_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    rook_attacks(sq, occupied) | bishop_attacks(sq, occupied)
+}
+
+/// Squares strictly between `a` and `b` if they share a rank, file or
+/// diagonal, otherwise none.
+pub fn between(a: Square, b: Square) -> Bitboard {
+    if rook_attacks(a, 0) & bit(b) != 0 {
+        rook_attacks(a, bit(b)) & rook_attacks(b, bit(a))
+    } else if bishop_attacks(a, 0) & bit(b) != 0 {
+        bishop_attacks(a, bit(b)) & bishop_attacks(b, bit(a))
+    } else {
+        0
+    }
+}
+
+/// The whole rank, file or diagonal through `a` and `b`, or none if they
+/// are not aligned.
+pub fn line(a: Square, b: Square) -> Bitboard {
+    let ends = bit(a) | bit(b);
+    if rook_attacks(a, 0) & bit(b) != 0 {
+        (rook_attacks(a, 0) & rook_attacks(b, 0)) | ends
+    } else if bishop_attacks(a, 0) & bit(b) != 0 {
+        (bishop_attacks(a, 0) & bishop_attacks(b, 0)) | ends
+    } else {
+        0
+    }
+}
+
+/// Piece sets for each colour and kind, kept in sync with the board.
+///
+/// Colours are indexed white then black and kinds as pawn, knight, bishop,
+/// rook, queen, king.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bitboards {
+    pub pieces: [[Bitboard; 6]; 2],
+    pub colours: [Bitboard; 2],
+    pub occupied: Bitboard,
+}
+
+pub(crate) const PAWN: usize = PieceKind::Pawn.index();
+pub(crate) const KNIGHT: usize = PieceKind::Knight.index();
+pub(crate) const BISHOP: usize = PieceKind::Bishop.index();
+pub(crate) const ROOK: usize = PieceKind::Rook.index();
+pub(crate) const QUEEN: usize = PieceKind::Queen.index();
+pub(crate) const KING: usize = PieceKind::King.index();
+
+impl Bitboards {
+    /// Adds `piece` on `square`, which must be empty.
+    pub(crate) fn add(&mut self, square: Square, piece: Piece) {
+        let b = bit(square);
+        self.pieces[piece.color.index()][piece.kind.index()] |= b;
+        self.colours[piece.color.index()] |= b;
+        self.occupied |= b;
+    }
+
+    /// Removes `piece` from `square`.
+    pub(crate) fn remove(&mut self, square: Square, piece: Piece) {
+        let b = !bit(square);
+        self.pieces[piece.color.index()][piece.kind.index()] &= b;
+        self.colours[piece.color.index()] &= b;
+        self.occupied &= b;
+    }
+
+    /// The squares of `piece`.
+    pub fn of(&self, piece: Piece) -> Bitboard {
+        self.pieces[piece.color.index()][piece.kind.index()]
+    }
+
+    /// Squares holding a piece of colour `by` that attacks `square`.
+    pub fn attackers(&self, square: Square, by: Color) -> Bitboard {
+        self.attackers_through(square, by, self.occupied)
+    }
+
+    /// Like [`Bitboards::attackers`], but as if exactly the squares in
+    /// `occupied` held pieces. Attackers outside `occupied` are ignored.
+    pub fn attackers_through(&self, square: Square, by: Color, occupied: Bitboard) -> Bitboard {
+        let sq = square.index();
+        let theirs = &self.pieces[by.index()];
+        let queens = theirs[QUEEN];
+
+        ((KNIGHT_ATTACKS[sq] & theirs[KNIGHT])
+            | (KING_ATTACKS[sq] & theirs[KING])
+            | (PAWN_ATTACKS[(!by).index()][sq] & theirs[PAWN])
+            | (bishop_attacks(square, occupied) & (theirs[BISHOP] | queens))
+            | (rook_attacks(square, occupied) & (theirs[ROOK] | queens)))
+            & occupied
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Color, File, Game, Piece, PieceKind, Square};
+
+/// The files of the two knights among the five squares left after placing
+/// the bishops and queen, in Scharnagl order.
+const KNIGHTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// The number of Chess960 starting positions.
+pub const CHESS960_POSITIONS: usize = 960;
+
+/// The back rank of Chess960 position `index`, numbered as by Scharnagl so
+/// that 518 is the standard position.
+fn back_rank(index: usize) -> [PieceKind; 8] {
+    let mut rank = [None; 8];
+    let mut n = index;
+
+    rank[2 * (n % 4) + 1] = Some(PieceKind::Bishop);
+    n /= 4;
+    rank[2 * (n % 4)] = Some(PieceKind::Bishop);
+    n /= 4;
+
+    let empty = |rank: &[Option<PieceKind>; 8]| -> Vec<usize> {
+        (0..8).filter(|&x| rank[x].is_none()).collect()
+    };
+    rank[empty(&rank)[n % 6]] = Some(PieceKind::Queen);
+    n /= 6;
+
+    let (a, b) = KNIGHTS[n];
+    let free = empty(&rank);
+    rank[free[a]] = Some(PieceKind::Knight);
+    rank[free[b]] = Some(PieceKind::Knight);
+
+    let free = empty(&rank);
+    for (x, kind) in free
+        .into_iter()
+        .zip([PieceKind::Rook, PieceKind::King, PieceKind::Rook])
+    {
+        rank[x] = Some(kind);
+    }
+
+    rank.map(Option::unwrap)
+}
+
+impl Game {
+    /// Sets up Chess960 starting position `index`, from 0 to 959, with both
+    /// sides mirroring each other and all castling rights.
+    ///
+    /// Positions are numbered as by Scharnagl, so 518 is the standard
+    /// starting position. Returns `None` if `index` is out of range.
+    pub fn from_960(index: usize) -> Option<Game> {
+        if index >= CHESS960_POSITIONS {
+            return None;
+        }
+
+        let mut game = Game::new();
+        let back = back_rank(index);
+        for (file, kind) in File::all().zip(back) {
+            for c in Color::ALL {
+                let pawn = Piece::new(PieceKind::Pawn, c);
+                game.set(Square::new(file, c.back_rank()), Some(Piece::new(kind, c)));
+                game.set(Square::new(file, c.pawn_rank()), Some(pawn));
+            }
+        }
+
+        let rooks: Vec<File> = File::all()
+            .filter(|file| back[file.index()] == PieceKind::Rook)
+            .collect();
+        game.castling_rooks = [[rooks[1], rooks[0]]; 2];
+        game.chess960 = true;
+        game.key = game.compute_key();
+        game.start_fen = Some(game.to_fen());
+        Some(game)
+    }
+}
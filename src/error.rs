@@ -0,0 +1,91 @@
+use crate::{ParseMoveError, PieceKind, Square};
+use std::fmt;
+
+/// Why a move was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// There is no piece on the starting square.
+    NoPieceOnSquare(Square),
+    /// The piece belongs to the side not to move.
+    WrongSide,
+    /// The target square holds a piece of the same colour.
+    CaptureOwnPiece,
+    /// The piece cannot move that way, even on an empty board.
+    IllegalPieceMove,
+    /// Another piece stands in the way.
+    BlockedPath,
+    /// The move would leave the mover's king attacked.
+    LeavesKingInCheck,
+    /// The king or that rook has already moved.
+    CastlingRightsLost,
+    /// The king is in check and may not castle.
+    CastlingOutOfCheck,
+    /// The king would pass through or land on an attacked square.
+    CastlingThroughCheck,
+    /// The promotion piece is not a queen, rook, bishop or knight, or the
+    /// move is not a pawn reaching the last rank.
+    InvalidPromotion(PieceKind),
+    /// A pawn reaching the last rank was not given a piece to promote to.
+    MissingPromotion,
+    /// The flags of a [`Move`](crate::Move) do not match what it does in
+    /// the position.
+    InconsistentMove,
+    /// More than one legal move matches the SAN given.
+    Ambiguous(String),
+    /// No legal move matches the SAN given.
+    NoMatchingMove(String),
+    /// Malformed Standard Algebraic Notation, with the reason.
+    InvalidSan(String),
+    /// Malformed long algebraic notation.
+    ParseError(ParseMoveError),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::NoPieceOnSquare(square) => {
+                write!(f, "Invalid move; No piece on {}", square)
+            }
+            MoveError::WrongSide => write!(f, "Invalid turn"),
+            MoveError::CaptureOwnPiece => write!(f, "Illegal move; Cannot capture own piece"),
+            MoveError::IllegalPieceMove => write!(f, "Illegal move; Piece cannot move there"),
+            MoveError::BlockedPath => write!(f, "Illegal move; Path is blocked"),
+            MoveError::LeavesKingInCheck => write!(f, "Illegal move; Places King in check"),
+            MoveError::CastlingRightsLost => write!(f, "Illegal move; Castling rights lost"),
+            MoveError::CastlingOutOfCheck => write!(f, "Illegal move; Cannot castle out of check"),
+            MoveError::CastlingThroughCheck => {
+                write!(f, "Illegal move; Cannot castle through check")
+            }
+            MoveError::InvalidPromotion(kind) => {
+                write!(
+                    f,
+                    "Invalid promotion '{}', try 'q', 'r', 'n', 'b'",
+                    kind.to_char()
+                )
+            }
+            MoveError::MissingPromotion => write!(f, "Invalid move; Missing promotion piece"),
+            MoveError::InconsistentMove => {
+                write!(f, "Invalid move; Flags do not match the position")
+            }
+            MoveError::Ambiguous(san) => write!(f, "Ambiguous move '{}'", san),
+            MoveError::NoMatchingMove(san) => write!(f, "Illegal move '{}'", san),
+            MoveError::InvalidSan(reason) => write!(f, "Invalid move: {}", reason),
+            MoveError::ParseError(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MoveError::ParseError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseMoveError> for MoveError {
+    fn from(e: ParseMoveError) -> Self {
+        MoveError::ParseError(e)
+    }
+}
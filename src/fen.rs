@@ -0,0 +1,278 @@
+use crate::{Color, File, Game, Piece, PieceKind, Rank, Square};
+
+/// The standard starting position in Forsyth-Edwards Notation.
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+impl Game {
+    /// Loads a position from a FEN string.
+    ///
+    /// All six fields are required. The position must have exactly one king
+    /// per side, no pawns on the first or last rank, no check against the
+    /// side that just moved, and castling rights only where the king and
+    /// rook are still on their home squares.
+    ///
+    /// Castling rights may also be given in X-FEN or Shredder-FEN, naming
+    /// the rook's file as in `HAha`, where `K` and `Q` stand for the
+    /// outermost rook on that side of the king. The game is treated as
+    /// Chess960 when a file is named or a right belongs to a king or rook
+    /// off its standard square.
+    pub fn from_fen(fen: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() != 6 {
+            return Err(format!(
+                "FEN must have 6 fields, found {} in '{}'",
+                fields.len(),
+                fen
+            ));
+        }
+
+        let mut game = Game::new();
+
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(format!(
+                "FEN board must have 8 ranks, found {}",
+                ranks.len()
+            ));
+        }
+        for (rank, y) in ranks.iter().zip(Rank::all().map(Rank::mirror)) {
+            let mut x = 0;
+            for c in rank.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    if !(1..=8).contains(&empty) {
+                        return Err(format!("Invalid empty square count '{}' on rank {}", c, y));
+                    }
+                    x += empty as usize;
+                } else {
+                    let piece = Piece::from_fen_char(c)
+                        .ok_or_else(|| format!("Invalid piece '{}' on rank {}", c, y))?;
+                    if let Some(file) = File::new(x) {
+                        game.set(Square::new(file, y), Some(piece));
+                    }
+                    x += 1;
+                }
+                if x > 8 {
+                    return Err(format!("Rank {} has more than 8 squares", y));
+                }
+            }
+            if x != 8 {
+                return Err(format!("Rank {} has {} squares, expected 8", y, x));
+            }
+        }
+
+        for c in Color::ALL {
+            let kings = game
+                .bitboards
+                .of(Piece::new(PieceKind::King, c))
+                .count_ones();
+            if kings != 1 {
+                return Err(format!("{} must have exactly one king, found {}", c, kings));
+            }
+        }
+        for y in [Rank::FIRST, Rank::EIGHTH] {
+            if File::all().any(|x| game.kind(Square::new(x, y)) == Some(PieceKind::Pawn)) {
+                return Err(format!("Pawns cannot stand on rank {}", y));
+            }
+        }
+
+        game.turn = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            s => return Err(format!("Invalid side to move '{}', expected 'w' or 'b'", s)),
+        };
+        let king = game.find(Piece::new(PieceKind::King, !game.turn)).unwrap();
+        if game.check(king, !game.turn) {
+            return Err(format!(
+                "{} is in check but it is {} to move",
+                !game.turn, game.turn
+            ));
+        }
+
+        game.castle = [[false; 2]; 2];
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let colour = if c.is_ascii_uppercase() {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                let y = colour.back_rank();
+                let king = game
+                    .find(Piece::new(PieceKind::King, colour))
+                    .filter(|king| king.rank() == y)
+                    .ok_or_else(|| {
+                        format!("Castling right '{}' requires the king on its back rank", c)
+                    })?;
+                let (side, rook) = match c.to_ascii_lowercase() {
+                    'k' => (0, game.outer_rook(colour, 0)),
+                    'q' => (1, game.outer_rook(colour, 1)),
+                    letter => {
+                        let file = File::from_char(letter)
+                            .ok_or_else(|| format!("Invalid castling right '{}'", c))?;
+                        let side = if file > king.file() { 0 } else { 1 };
+                        let rook = Some(Piece::new(PieceKind::Rook, colour));
+                        game.chess960 = true;
+                        (
+                            side,
+                            (game.piece(Square::new(file, y)) == rook).then_some(file),
+                        )
+                    }
+                };
+                let rook = rook.ok_or_else(|| {
+                    format!(
+                        "Castling right '{}' requires a rook on that side of the king",
+                        c
+                    )
+                })?;
+                if game.castle[colour.index()][side] {
+                    return Err(format!("Duplicate castling right '{}'", c));
+                }
+                if king.file() != File::E || rook != [File::H, File::A][side] {
+                    game.chess960 = true;
+                }
+                game.castle[colour.index()][side] = true;
+                game.castling_rooks[colour.index()][side] = rook;
+            }
+        }
+
+        if fields[3] != "-" {
+            let target: Square = fields[3]
+                .parse()
+                .map_err(|_| format!("Invalid en passant square '{}'", fields[3]))?;
+            let (target_rank, pawn_rank) = match game.turn {
+                Color::White => (Rank::SIXTH, Rank::FIFTH),
+                Color::Black => (Rank::THIRD, Rank::FOURTH),
+            };
+            if target.rank() != target_rank {
+                return Err(format!(
+                    "En passant square '{}' is not on rank {}",
+                    fields[3], target_rank
+                ));
+            }
+            let pawn = Square::new(target.file(), pawn_rank);
+            if game.piece(pawn) != Some(Piece::new(PieceKind::Pawn, !game.turn)) {
+                return Err(format!(
+                    "En passant square '{}' has no pawn that just moved two squares",
+                    fields[3]
+                ));
+            }
+            game.en_passant = Some(pawn);
+        }
+
+        game.halfmove = fields[4]
+            .parse()
+            .map_err(|_| format!("Invalid halfmove clock '{}'", fields[4]))?;
+        game.counter = match fields[5].parse() {
+            Ok(n) if n >= 1 => n,
+            _ => return Err(format!("Invalid fullmove number '{}'", fields[5])),
+        };
+
+        game.key = game.compute_key();
+        game.start_fen = Some(game.to_fen());
+        Ok(game)
+    }
+
+    /// Writes the position as a FEN string.
+    ///
+    /// In Chess960 the castling rights follow X-FEN: `K` and `Q` where the
+    /// rook is the outermost one on its side of the king, and the rook's
+    /// file otherwise.
+    pub fn to_fen(&self) -> String {
+        self.write_fen(false)
+    }
+
+    /// Writes the position as a Shredder-FEN string, which names the file
+    /// of every castling rook, as in `HAha`.
+    pub fn to_shredder_fen(&self) -> String {
+        self.write_fen(true)
+    }
+
+    fn write_fen(&self, shredder: bool) -> String {
+        let mut fen = String::new();
+
+        for y in Rank::all().map(Rank::mirror) {
+            let mut empty = 0;
+            for x in File::all() {
+                let Some(piece) = self.piece(Square::new(x, y)) else {
+                    empty += 1;
+                    continue;
+                };
+                if empty > 0 {
+                    fen.push_str(&empty.to_string());
+                    empty = 0;
+                }
+                fen.push(piece.fen_char());
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if y != Rank::FIRST {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(match self.turn {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        let mut castle = String::new();
+        for colour in Color::ALL {
+            for side in [0, 1] {
+                if !self.castle[colour.index()][side] {
+                    continue;
+                }
+                let file = self.castling_rooks[colour.index()][side];
+                let letter =
+                    if shredder || (self.chess960 && self.outer_rook(colour, side) != Some(file)) {
+                        file.to_char()
+                    } else {
+                        ['k', 'q'][side]
+                    };
+                castle.push(match colour {
+                    Color::White => letter.to_ascii_uppercase(),
+                    Color::Black => letter,
+                });
+            }
+        }
+        fen.push_str(if castle.is_empty() { "-" } else { &castle });
+
+        match self.en_passant_target() {
+            Some(square) => fen.push_str(&format!(" {}", square)),
+            None => fen.push_str(" -"),
+        }
+
+        fen.push_str(&format!(" {} {}", self.halfmove, self.counter));
+        fen
+    }
+
+    /// The file of the outermost rook of colour `c` on `side` of its king,
+    /// where side 0 is the king side, if the king is on its back rank.
+    fn outer_rook(&self, c: Color, side: usize) -> Option<File> {
+        let y = c.back_rank();
+        let king = self.find(Piece::new(PieceKind::King, c))?;
+        if king.rank() != y {
+            return None;
+        }
+        let rook = Some(Piece::new(PieceKind::Rook, c));
+        let mut rooks = File::all()
+            .filter(|&x| (x > king.file()) == (side == 0) && x != king.file())
+            .filter(|&x| self.piece(Square::new(x, y)) == rook);
+        if side == 0 {
+            rooks.last()
+        } else {
+            rooks.next()
+        }
+    }
+
+    /// The square a pawn can capture onto en passant this turn, if any.
+    pub fn en_passant_target(&self) -> Option<Square> {
+        let pawn = self.en_passant?;
+        let rank = if pawn.rank() == Rank::FOURTH {
+            Rank::THIRD
+        } else {
+            Rank::SIXTH
+        };
+        Some(Square::new(pawn.file(), rank))
+    }
+}
@@ -0,0 +1,391 @@
+use crate::bitboard::{self, Bitboards};
+use crate::zobrist::piece_key;
+use crate::{Color, File, Move, MoveError, Piece, PieceKind, Rank, Square, State, Undo};
+
+/// A chess position together with the bookkeeping needed to validate moves.
+#[derive(Clone)]
+pub struct Game {
+    pub(crate) board: [Option<Piece>; 64],
+    pub(crate) bitboards: Bitboards,
+    pub(crate) castle: [[bool; 2]; 2], // 0-W, 1-B
+    /// The file each castling rook starts on, as `[colour][side]`.
+    pub(crate) castling_rooks: [[File; 2]; 2],
+    /// Whether castling moves are written king-takes-rook, as Chess960
+    /// requires.
+    pub(crate) chess960: bool,
+    pub(crate) promotion: PieceKind,
+    pub(crate) turn: Color,
+    pub(crate) en_passant: Option<Square>,
+    pub(crate) halfmove: usize,
+    pub(crate) counter: usize,
+    pub(crate) key: u64,
+    pub(crate) history: Vec<Undo>,
+    pub(crate) start_fen: Option<String>,
+    /// Whether a player has claimed a draw available in this position.
+    pub(crate) draw_claimed: bool,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    /// Creates an empty board with white to move. Call [`Game::init`] to set up
+    /// the standard starting position.
+    pub fn new() -> Self {
+        let mut game = Self {
+            board: [None; 64],
+            bitboards: Bitboards::default(),
+            castle: [[true; 2]; 2],
+            castling_rooks: [[File::H, File::A]; 2],
+            chess960: false,
+            promotion: PieceKind::Queen,
+            turn: Color::White,
+            en_passant: None,
+            halfmove: 0,
+            counter: 1,
+            key: 0,
+            history: Vec::new(),
+            start_fen: None,
+            draw_claimed: false,
+        };
+        game.key = game.compute_key();
+        game
+    }
+
+    /// Places the pieces on their standard starting squares.
+    pub fn init(&mut self) {
+        let back = [
+            PieceKind::Rook,
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Queen,
+            PieceKind::King,
+            PieceKind::Bishop,
+            PieceKind::Knight,
+            PieceKind::Rook,
+        ];
+        for (file, kind) in File::all().zip(back) {
+            for c in Color::ALL {
+                let pawn = Piece::new(PieceKind::Pawn, c);
+                self.set(Square::new(file, c.back_rank()), Some(Piece::new(kind, c)));
+                self.set(Square::new(file, c.pawn_rank()), Some(pawn));
+            }
+        }
+    }
+
+    /// Puts `piece` on `square`, keeping the bitboards and the position key
+    /// in sync with the board.
+    pub(crate) fn set(&mut self, square: Square, piece: Option<Piece>) {
+        if let Some(old) = self.piece(square) {
+            self.bitboards.remove(square, old);
+            self.key ^= piece_key(square, old);
+        }
+        if let Some(new) = piece {
+            self.bitboards.add(square, new);
+            self.key ^= piece_key(square, new);
+        }
+        self.board[square.index()] = piece;
+    }
+
+    /// The piece on `square`, or `None` if it is empty.
+    pub fn piece(&self, square: Square) -> Option<Piece> {
+        self.board[square.index()]
+    }
+
+    /// The kind of the piece on `square`, or `None` if it is empty.
+    pub(crate) fn kind(&self, square: Square) -> Option<PieceKind> {
+        self.board[square.index()].map(|piece| piece.kind)
+    }
+
+    /// The piece sets for each colour and kind.
+    pub fn bitboards(&self) -> &Bitboards {
+        &self.bitboards
+    }
+
+    /// The content of every square, indexed by [`Square::index`].
+    pub fn board(&self) -> &[Option<Piece>; 64] {
+        &self.board
+    }
+
+    /// The side to move.
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    /// The fullmove number, starting at 1 and incremented after black moves.
+    pub fn counter(&self) -> usize {
+        self.counter
+    }
+
+    /// The number of halfmoves since the last capture or pawn move.
+    pub fn halfmove_clock(&self) -> usize {
+        self.halfmove
+    }
+
+    /// Castling rights as `[colour.index()][side]`, where side 0 is the king
+    /// side.
+    pub fn castle(&self) -> [[bool; 2]; 2] {
+        self.castle
+    }
+
+    /// The file of the rook each castling right belongs to, as
+    /// `[colour.index()][side]`. Always the h and a files outside Chess960.
+    pub fn castling_rooks(&self) -> [[File; 2]; 2] {
+        self.castling_rooks
+    }
+
+    /// Whether this is a Chess960 game. Castling moves are then written as
+    /// the king capturing its own rook, such as `e1h1`.
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// The piece pawns are promoted to.
+    pub fn promotion(&self) -> PieceKind {
+        self.promotion
+    }
+
+    /// Every move played so far, oldest first.
+    pub fn moves(&self) -> Vec<Move> {
+        self.history.iter().map(|undo| undo.mov).collect()
+    }
+
+    /// The undo information of every move played so far, oldest first.
+    pub fn history(&self) -> &[Undo] {
+        &self.history
+    }
+
+    /// Selects the piece pawns are promoted to: a queen, rook, bishop or
+    /// knight.
+    pub fn set_promotion(&mut self, promotion: PieceKind) -> Result<(), MoveError> {
+        if !PieceKind::PROMOTIONS.contains(&promotion) {
+            return Err(MoveError::InvalidPromotion(promotion));
+        }
+        self.promotion = promotion;
+        Ok(())
+    }
+
+    /// Validates and plays a move for the side to move.
+    ///
+    /// Pawns reaching the last rank are promoted to the piece selected with
+    /// [`Game::set_promotion`].
+    pub fn move_piece(&mut self, i: Square, f: Square) -> Result<(), MoveError> {
+        let mov = self.describe_move(i, f, self.default_promotion(i, f));
+        self.make_move(mov)?;
+        Ok(())
+    }
+
+    /// The selected promotion piece if moving the piece on `i` to `f` is a
+    /// pawn reaching the last rank.
+    pub(crate) fn default_promotion(&self, i: Square, f: Square) -> Option<PieceKind> {
+        match self.piece(i) {
+            Some(Piece {
+                kind: PieceKind::Pawn,
+                color,
+            }) if f.rank() == color.promotion_rank() => Some(self.promotion),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the square `i` is attacked by the opponent of colour `c`.
+    pub fn check(&self, i: Square, c: Color) -> bool {
+        self.attackers_of(i, !c) != 0
+    }
+
+    /// Returns the first square holding `piece`, if any.
+    pub fn find(&self, piece: Piece) -> Option<Square> {
+        bitboard::squares(self.bitboards.of(piece)).next()
+    }
+
+    /// Prints the board to stdout using ANSI colours. Both sides are drawn
+    /// with filled glyphs, black pieces in black.
+    pub fn display(&self) {
+        for (i, rank) in Rank::all().map(Rank::mirror).enumerate() {
+            print!("\x1b[38;5;15m\x1b[48;5;236m{} \x1b[0m", rank);
+            for (j, file) in File::all().enumerate() {
+                let (colour, glyph) = match self.piece(Square::new(file, rank)) {
+                    Some(piece) => (
+                        if piece.color == Color::Black {
+                            "\x1b[38;5;16m"
+                        } else {
+                            ""
+                        },
+                        Piece::new(piece.kind, Color::Black).unicode(),
+                    ),
+                    None => ("", ' '),
+                };
+                print!(
+                    "{}{}{} \x1b[0m",
+                    if (j % 2 == 1) ^ (i % 2 == 0) {
+                        "\x1b[48;5;250m"
+                    } else {
+                        "\x1b[48;5;240m"
+                    },
+                    colour,
+                    glyph
+                );
+            }
+            println!();
+        }
+        print!("\x1b[38;5;15m\x1b[48;5;236m ");
+        for file in File::all() {
+            print!("\x1b[38;5;15m\x1b[48;5;236m {}\x1b[0m", file);
+        }
+        println!("\x1b[38;5;15m\x1b[48;5;236m \x1b[0m")
+    }
+
+    /// The board as plain ASCII text, eighth rank first, with FEN letters
+    /// for pieces and `.` for empty squares.
+    pub fn to_ascii(&self) -> String {
+        self.render(Piece::fen_char, '.')
+    }
+
+    /// The board as text with Unicode chess glyphs, eighth rank first and
+    /// `·` for empty squares.
+    pub fn to_unicode(&self) -> String {
+        self.render(Piece::unicode, '·')
+    }
+
+    /// Draws the board one rank per line with file letters underneath.
+    fn render(&self, glyph: fn(Piece) -> char, empty: char) -> String {
+        let mut text = String::new();
+        for rank in Rank::all().map(Rank::mirror) {
+            text.push(rank.to_char());
+            for file in File::all() {
+                text.push(' ');
+                text.push(self.piece(Square::new(file, rank)).map_or(empty, glyph));
+            }
+            text.push('\n');
+        }
+        text.push(' ');
+        for file in File::all() {
+            text.push(' ');
+            text.push(file.to_char());
+        }
+        text.push('\n');
+        text
+    }
+
+    /// Reports a dead position when neither side has the material to mate:
+    /// king against king, king and a single minor piece against king, or
+    /// kings and bishops that all stand on squares of the same colour.
+    pub fn check_remaining_pieces(&self) -> State {
+        let mut minors = 0;
+        let mut bishop_squares = [false; 2];
+        let mut knights = false;
+
+        for square in Square::all() {
+            match self.kind(square) {
+                None | Some(PieceKind::King) => {}
+                Some(PieceKind::Bishop) => {
+                    minors += 1;
+                    bishop_squares[(square.rank().index() + square.file().index()) % 2] = true;
+                }
+                Some(PieceKind::Knight) => {
+                    minors += 1;
+                    knights = true;
+                }
+                _ => return State::Continue,
+            }
+        }
+
+        if minors <= 1 || (!knights && bishop_squares != [true; 2]) {
+            return State::DeadPosition;
+        }
+
+        State::Continue
+    }
+
+    /// Determines whether the side to move is in check, mated or stalemated.
+    /// A check is reported ahead of a draw that could be claimed, which is
+    /// also available from [`Game::claimable_draw`].
+    pub fn check_game_end(&self) -> State {
+        let king_white = self
+            .find(Piece::new(PieceKind::King, Color::White))
+            .unwrap();
+        let king_black = self
+            .find(Piece::new(PieceKind::King, Color::Black))
+            .unwrap();
+
+        let white_check = self.check(king_white, Color::White);
+        let black_check = self.check(king_black, Color::Black);
+
+        let crp = self.check_remaining_pieces();
+
+        match crp {
+            State::Continue => {}
+            _ => return crp,
+        };
+
+        if self.legal_moves().next().is_none() {
+            return match (self.turn, white_check, black_check) {
+                (Color::White, true, _) => State::WhiteCheckmate,
+                (Color::White, false, _) => State::WhiteStalemate,
+                (Color::Black, _, true) => State::BlackCheckmate,
+                (Color::Black, _, false) => State::BlackStalemate,
+            };
+        }
+
+        if self.repetitions() >= 5 {
+            return State::FivefoldRepetition;
+        }
+        if self.halfmove >= 150 {
+            return State::SeventyFiveMoveRule;
+        }
+
+        if white_check {
+            return State::WhiteCheck;
+        }
+
+        if black_check {
+            return State::BlackCheck;
+        }
+
+        self.claimable_draw().unwrap_or(State::Continue)
+    }
+
+    /// Returns the squares of every piece belonging to colour `c`.
+    pub fn get_pieces(&self, c: Color) -> Vec<Square> {
+        bitboard::squares(self.bitboards.colours[c.index()]).collect()
+    }
+
+    /// Returns whether the game has ended by mate, stalemate or a draw. A
+    /// threefold repetition or fifty-move draw only ends the game once it
+    /// has been claimed.
+    pub fn is_game_over(&self) -> bool {
+        self.draw_claimed
+            || matches!(
+                self.check_game_end(),
+                State::WhiteCheckmate
+                    | State::WhiteStalemate
+                    | State::BlackCheckmate
+                    | State::BlackStalemate
+                    | State::DeadPosition
+                    | State::FivefoldRepetition
+                    | State::SeventyFiveMoveRule
+            )
+    }
+
+    /// The draw the side to move may claim in this position, if any.
+    pub fn claimable_draw(&self) -> Option<State> {
+        if self.repetitions() >= 3 {
+            Some(State::ThreefoldRepetition)
+        } else if self.halfmove >= 100 {
+            Some(State::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    /// Claims the draw available in this position, ending the game. Returns
+    /// the rule it was claimed under, or `None` when there is nothing to
+    /// claim.
+    pub fn claim_draw(&mut self) -> Option<State> {
+        let draw = self.claimable_draw()?;
+        self.draw_claimed = true;
+        Some(draw)
+    }
+}
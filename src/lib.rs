@@ -0,0 +1,39 @@
+//! A small chess board, move validator and minimax AI.
+//!
+//! The [`Game`] type holds the position and validates moves, [`Piece`]
+//! describes what sits on each square and [`State`] reports checks and
+//! game endings. Squares and sides are named with [`Square`] and [`Color`].
+
+mod ai;
+mod attacks;
+mod bitboard;
+mod chess960;
+mod error;
+mod fen;
+mod game;
+mod magic;
+mod movegen;
+mod moves;
+mod perft;
+mod pgn;
+mod piece;
+mod repetition;
+mod san;
+mod see;
+mod square;
+mod state;
+mod uci;
+mod zobrist;
+
+pub use bitboard::{Bitboard, Bitboards};
+pub use chess960::CHESS960_POSITIONS;
+pub use error::MoveError;
+pub use fen::START_FEN;
+pub use game::Game;
+pub use movegen::LegalMoves;
+pub use moves::{Move, Undo};
+pub use pgn::{PgnGame, PgnTags, parse_pgn};
+pub use piece::{Piece, PieceKind};
+pub use square::{Color, File, Rank, Square};
+pub use state::State;
+pub use uci::ParseMoveError;
@@ -0,0 +1,125 @@
+use crate::bitboard::{BISHOP_DIRECTIONS, Bitboard, ROOK_DIRECTIONS, sliding_attacks};
+use std::sync::LazyLock;
+
+/// Rook attack lookups, built the first time they are used.
+pub(crate) static ROOK: LazyLock<Magics> = LazyLock::new(|| Magics::new(&ROOK_DIRECTIONS));
+
+/// Bishop attack lookups, built the first time they are used.
+pub(crate) static BISHOP: LazyLock<Magics> = LazyLock::new(|| Magics::new(&BISHOP_DIRECTIONS));
+
+#[derive(Clone, Copy, Default)]
+struct Entry {
+    mask: Bitboard,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+/// Magic bitboard tables for one kind of slider.
+///
+/// For each square the relevant blockers are masked out of the occupancy and
+/// multiplied by a magic number, whose top bits index a table of
+/// precomputed attack sets.
+pub(crate) struct Magics {
+    entries: [Entry; 64],
+    attacks: Vec<Bitboard>,
+}
+
+/// Per-rank seeds known to find magics for every square quickly.
+const SEEDS: [u64; 8] = [728, 10316, 55013, 32803, 12281, 15100, 16645, 255];
+
+/// A small xorshift* generator so the magics found are the same every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(2_685_821_657_736_338_717)
+    }
+
+    /// Candidates with few bits set make good magics.
+    fn sparse(&mut self) -> u64 {
+        self.next() & self.next() & self.next()
+    }
+}
+
+/// The squares whose occupancy can change a slider's attacks from `sq`:
+/// every ray square except the last one before the edge.
+fn relevant_mask(sq: usize, directions: &[(isize, isize)]) -> Bitboard {
+    let mut mask = 0;
+    for (dy, dx) in directions {
+        let mut y = (sq / 8) as isize + dy;
+        let mut x = (sq % 8) as isize + dx;
+        while (0..8).contains(&(y + dy)) && (0..8).contains(&(x + dx)) {
+            mask |= 1 << (y * 8 + x);
+            y += dy;
+            x += dx;
+        }
+    }
+    mask
+}
+
+impl Magics {
+    fn new(directions: &[(isize, isize)]) -> Self {
+        let mut entries = [Entry::default(); 64];
+        let mut attacks = Vec::new();
+
+        for (sq, entry) in entries.iter_mut().enumerate() {
+            let mask = relevant_mask(sq, directions);
+            let bits = mask.count_ones();
+
+            let mut occupancies = Vec::with_capacity(1 << bits);
+            let mut subset: Bitboard = 0;
+            loop {
+                occupancies.push((subset, sliding_attacks(sq, subset, directions)));
+                subset = subset.wrapping_sub(mask) & mask;
+                if subset == 0 {
+                    break;
+                }
+            }
+
+            let shift = 64 - bits;
+            let mut rng = Rng(SEEDS[sq / 8]);
+            let mut table = vec![0; 1 << bits];
+            let mut epoch = vec![0u32; 1 << bits];
+            let mut attempt = 0;
+            let magic = loop {
+                let magic = rng.sparse();
+                if (mask.wrapping_mul(magic) >> 56).count_ones() < 6 {
+                    continue;
+                }
+                attempt += 1;
+                let fits = occupancies.iter().all(|&(occupied, attack)| {
+                    let index = (occupied.wrapping_mul(magic) >> shift) as usize;
+                    if epoch[index] != attempt {
+                        epoch[index] = attempt;
+                        table[index] = attack;
+                    }
+                    table[index] == attack
+                });
+                if fits {
+                    break magic;
+                }
+            };
+
+            *entry = Entry {
+                mask,
+                magic,
+                shift,
+                offset: attacks.len(),
+            };
+            attacks.extend(table);
+        }
+
+        Self { entries, attacks }
+    }
+
+    /// The attack set from `sq` given the occupied squares.
+    pub(crate) fn attacks(&self, sq: usize, occupied: Bitboard) -> Bitboard {
+        let entry = &self.entries[sq];
+        let index = ((occupied & entry.mask).wrapping_mul(entry.magic) >> entry.shift) as usize;
+        self.attacks[entry.offset + index]
+    }
+}
@@ -0,0 +1,332 @@
+use crate::bitboard::{self, BISHOP, Bitboard, QUEEN, ROOK};
+use crate::moves::castled_squares;
+use crate::{Game, Move, MoveError, Piece, PieceKind, Square};
+
+/// An iterator over the legal moves of the side to move, produced lazily so
+/// callers that only need the first move stop early.
+///
+/// Checkers and pinned pieces are found once up front, so each move is
+/// checked against a few masks instead of being played out.
+///
+/// Created by [`Game::legal_moves`].
+pub struct LegalMoves<'a> {
+    game: &'a Game,
+    pieces: Bitboard,
+    from: Square,
+    targets: Bitboard,
+    /// Promotions and castling moves waiting to be yielded.
+    pending: Vec<Move>,
+    king: Square,
+    checkers: Bitboard,
+    pinned: Bitboard,
+    /// Squares a piece other than the king may move to: anywhere when not
+    /// in check, the checker or a square blocking it in single check, and
+    /// nowhere in double check.
+    evasions: Bitboard,
+}
+
+impl Iterator for LegalMoves<'_> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        loop {
+            if let Some(mov) = self.pending.pop() {
+                return Some(mov);
+            }
+
+            if self.targets == 0 {
+                if self.pieces == 0 {
+                    return None;
+                }
+                self.from = bitboard::first(self.pieces);
+                self.pieces &= self.pieces - 1;
+                self.targets = self.game.candidate_targets(self.from);
+                if self.from == self.king {
+                    for side in [1, 0] {
+                        if self.castling_error(side).is_none() {
+                            let mov = self.game.describe_move(
+                                self.king,
+                                self.game.castling_target(self.game.turn, side),
+                                None,
+                            );
+                            self.pending.push(mov);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            let f = bitboard::first(self.targets);
+            self.targets &= self.targets - 1;
+            if !self.keeps_king_safe(self.from, f) {
+                continue;
+            }
+
+            let mov = self.game.describe_move(self.from, f, None);
+            if self.game.kind(self.from) == Some(PieceKind::Pawn)
+                && f.rank() == self.game.turn.promotion_rank()
+            {
+                self.pending
+                    .extend(PieceKind::PROMOTIONS.into_iter().rev().map(|p| Move {
+                        promotion: Some(p),
+                        ..mov
+                    }));
+                continue;
+            }
+            return Some(mov);
+        }
+    }
+}
+
+impl LegalMoves<'_> {
+    /// Returns whether moving the piece on `i` to `f`, one of its candidate
+    /// targets, leaves the king safe.
+    fn keeps_king_safe(&self, i: Square, f: Square) -> bool {
+        let game = self.game;
+        let them = !game.turn;
+        let occupied = game.bitboards.occupied;
+
+        match game.kind(i) {
+            Some(PieceKind::King) => {
+                game.bitboards
+                    .attackers_through(f, them, occupied & !bitboard::bit(i))
+                    == 0
+            }
+            Some(PieceKind::Pawn) if i.file() != f.file() && game.piece(f).is_none() => {
+                // En passant empties two squares on the capturing pawn's
+                // rank, so play it out on the occupancy instead.
+                let captured = Square::new(f.file(), i.rank());
+                let after =
+                    (occupied & !bitboard::bit(i) & !bitboard::bit(captured)) | bitboard::bit(f);
+                game.bitboards.attackers_through(self.king, them, after) == 0
+            }
+            _ => {
+                self.evasions & bitboard::bit(f) != 0
+                    && (self.pinned & bitboard::bit(i) == 0
+                        || bitboard::line(self.king, i) & bitboard::bit(f) != 0)
+            }
+        }
+    }
+
+    /// Checks that the king may castle to `side`, where side 0 is the king
+    /// side: the right is still held, every square either piece crosses or
+    /// lands on is empty apart from the two of them, and the king is not in
+    /// check and does not pass through or land on an attacked square.
+    ///
+    /// The king and rook are lifted before looking for attacks, as a rook
+    /// that shielded the king must not count once it has moved.
+    fn castling_error(&self, side: usize) -> Option<MoveError> {
+        let game = self.game;
+        let c = game.turn;
+        let king = self.king;
+        let rook = game.castling_rook(c, side);
+        let (king_to, rook_to) = castled_squares(c, side);
+
+        if !game.castle[c.index()][side]
+            || king.rank() != c.back_rank()
+            || game.piece(rook) != Some(Piece::new(PieceKind::Rook, c))
+        {
+            return Some(MoveError::CastlingRightsLost);
+        }
+
+        let king_path = bitboard::between(king, king_to) | bitboard::bit(king_to);
+        let rook_path = bitboard::between(rook, rook_to) | bitboard::bit(rook_to);
+        let occupied = game.bitboards.occupied & !bitboard::bit(king) & !bitboard::bit(rook);
+        if (king_path | rook_path) & occupied != 0 {
+            Some(MoveError::BlockedPath)
+        } else if self.checkers != 0 {
+            Some(MoveError::CastlingOutOfCheck)
+        } else if bitboard::squares(king_path)
+            .any(|sq| game.bitboards.attackers_through(sq, !c, occupied) != 0)
+        {
+            Some(MoveError::CastlingThroughCheck)
+        } else {
+            None
+        }
+    }
+}
+
+impl Game {
+    /// Every legal move of the side to move. A pawn reaching the last rank
+    /// yields one move per promotion piece, queen first.
+    pub fn legal_moves(&self) -> LegalMoves<'_> {
+        self.legal_moves_of(self.bitboards.colours[self.turn.index()])
+    }
+
+    /// The legal moves of the pieces in `pieces`. Only pieces of the side to
+    /// move have any.
+    fn legal_moves_of(&self, pieces: Bitboard) -> LegalMoves<'_> {
+        let c = self.turn;
+        let us = c.index();
+        let king = self.find(Piece::new(PieceKind::King, c)).unwrap();
+        let checkers = self.bitboards.attackers(king, !c);
+
+        let theirs = &self.bitboards.pieces[(!c).index()];
+        let snipers = (bitboard::rook_attacks(king, 0) & (theirs[ROOK] | theirs[QUEEN]))
+            | (bitboard::bishop_attacks(king, 0) & (theirs[BISHOP] | theirs[QUEEN]));
+        let mut pinned = 0;
+        for sniper in bitboard::squares(snipers) {
+            let blockers = bitboard::between(king, sniper) & self.bitboards.occupied;
+            if blockers.count_ones() == 1 {
+                pinned |= blockers & self.bitboards.colours[us];
+            }
+        }
+
+        let evasions = match checkers.count_ones() {
+            0 => !0,
+            1 => {
+                let checker = bitboard::first(checkers);
+                checkers | bitboard::between(king, checker)
+            }
+            _ => 0,
+        };
+
+        LegalMoves {
+            game: self,
+            pieces: pieces & self.bitboards.colours[us],
+            from: king,
+            targets: 0,
+            pending: Vec::new(),
+            king,
+            checkers,
+            pinned,
+            evasions,
+        }
+    }
+
+    /// Returns whether the piece on `coords` has at least one legal move.
+    pub fn find_valid_move(&self, coords: Square) -> bool {
+        self.legal_moves_of(bitboard::bit(coords)).next().is_some()
+    }
+
+    /// Returns every legal move of the piece on `coords`.
+    pub fn get_valid_moves(&self, coords: Square) -> Vec<Move> {
+        self.legal_moves_of(bitboard::bit(coords)).collect()
+    }
+
+    /// Checks whether `mov` is legal for the side to move without changing
+    /// the game, and says why not if it isn't.
+    ///
+    /// Besides the squares, the promotion piece must be given exactly when a
+    /// pawn reaches the last rank, and the capture, castle, en passant and
+    /// double push flags must describe what the move does.
+    pub fn is_legal(&self, mov: Move) -> Result<(), MoveError> {
+        let Move { from: i, to: f, .. } = mov;
+        let piece = self.piece(i);
+        let legal = piece.map(|piece| piece.color) == Some(self.turn)
+            && match self.castling_side(i, f) {
+                Some(side) => self.castling_error(side).is_none(),
+                None => {
+                    self.candidate_targets(i) & bitboard::bit(f) != 0
+                        && self.legal_moves_of(bitboard::bit(i)).keeps_king_safe(i, f)
+                }
+            };
+        if !legal {
+            return Err(self.move_error(i, f));
+        }
+
+        let promotes = piece.is_some_and(|piece| piece.kind == PieceKind::Pawn)
+            && f.rank() == self.turn.promotion_rank();
+        match (promotes, mov.promotion) {
+            (true, None) => return Err(MoveError::MissingPromotion),
+            (true, Some(p)) if PieceKind::PROMOTIONS.contains(&p) => {}
+            (false, None) => {}
+            (_, Some(p)) => return Err(MoveError::InvalidPromotion(p)),
+        }
+
+        if mov != self.describe_move(i, f, mov.promotion) {
+            return Err(MoveError::InconsistentMove);
+        }
+        Ok(())
+    }
+
+    /// Explains why the side to move may not castle to `side`, or `None` if
+    /// it may.
+    pub(crate) fn castling_error(&self, side: usize) -> Option<MoveError> {
+        self.legal_moves_of(0).castling_error(side)
+    }
+
+    /// Explains why moving the piece on `i` to `f` is not a legal move.
+    pub(crate) fn move_error(&self, i: Square, f: Square) -> MoveError {
+        let Some(Piece { kind, color: c }) = self.piece(i) else {
+            return MoveError::NoPieceOnSquare(i);
+        };
+        if c != self.turn {
+            return MoveError::WrongSide;
+        }
+        if let Some(side) = self.castling_side(i, f) {
+            return self
+                .castling_error(side)
+                .unwrap_or(MoveError::LeavesKingInCheck);
+        }
+        if self.piece(f).is_some_and(|piece| piece.color == c) {
+            return MoveError::CaptureOwnPiece;
+        }
+        if self.candidate_targets(i) & bitboard::bit(f) != 0 {
+            return MoveError::LeavesKingInCheck;
+        }
+
+        let reach = match kind {
+            PieceKind::Bishop => bitboard::bishop_attacks(i, 0),
+            PieceKind::Rook => bitboard::rook_attacks(i, 0),
+            PieceKind::Queen => bitboard::queen_attacks(i, 0),
+            PieceKind::Pawn => {
+                let mut pushes = 0;
+                if let Some(ahead) = i.offset(0, c.forward()) {
+                    pushes |= bitboard::bit(ahead);
+                }
+                if i.rank() == c.pawn_rank()
+                    && let Some(two) = i.offset(0, 2 * c.forward())
+                {
+                    pushes |= bitboard::bit(two);
+                }
+                pushes
+            }
+            _ => 0,
+        };
+        if reach & bitboard::bit(f) != 0 {
+            MoveError::BlockedPath
+        } else {
+            MoveError::IllegalPieceMove
+        }
+    }
+
+    /// Squares the piece on `coords` attacks or could move to, ignoring
+    /// whether the move would leave its king in check. Castling is left to
+    /// [`LegalMoves`].
+    fn candidate_targets(&self, coords: Square) -> Bitboard {
+        let Some(Piece { kind, color: c }) = self.piece(coords) else {
+            return 0;
+        };
+        let sq = coords.index();
+        let own = self.bitboards.colours[c.index()];
+        let occupied = self.bitboards.occupied;
+
+        match kind {
+            PieceKind::Knight => bitboard::KNIGHT_ATTACKS[sq] & !own,
+            PieceKind::Bishop => bitboard::bishop_attacks(coords, occupied) & !own,
+            PieceKind::Rook => bitboard::rook_attacks(coords, occupied) & !own,
+            PieceKind::Queen => bitboard::queen_attacks(coords, occupied) & !own,
+            PieceKind::King => bitboard::KING_ATTACKS[sq] & !own,
+            PieceKind::Pawn => {
+                let mut targets = 0;
+                if let Some(ahead) = coords.offset(0, c.forward())
+                    && self.piece(ahead).is_none()
+                {
+                    targets |= bitboard::bit(ahead);
+                    if coords.rank() == c.pawn_rank()
+                        && let Some(two) = ahead.offset(0, c.forward())
+                        && self.piece(two).is_none()
+                    {
+                        targets |= bitboard::bit(two);
+                    }
+                }
+                let mut enemies = self.bitboards.colours[(!c).index()];
+                if let Some(target) = self.en_passant_target() {
+                    enemies |= bitboard::bit(target);
+                }
+                targets | (bitboard::PAWN_ATTACKS[c.index()][sq] & enemies)
+            }
+        }
+    }
+}
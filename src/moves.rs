@@ -0,0 +1,313 @@
+use crate::{Color, File, Game, MoveError, Piece, PieceKind, Square};
+use std::fmt;
+
+/// A move together with a description of what it does to the board.
+///
+/// Moves are produced by [`Game::legal_moves`] and played with
+/// [`Game::make_move`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub from: Square,
+    pub to: Square,
+    /// The piece a pawn promotes to: a queen, rook, bishop or knight.
+    pub promotion: Option<PieceKind>,
+    /// Whether a piece is captured, including en passant.
+    pub capture: bool,
+    /// Whether this is a king move that castles, moving the rook as well.
+    /// The target is then the rook's square in Chess960 games and the
+    /// king's destination otherwise.
+    pub castle: bool,
+    /// Whether a pawn captures en passant, removing the pawn beside it.
+    pub en_passant: bool,
+    /// Whether a pawn advances two squares from its starting rank.
+    pub double_push: bool,
+}
+
+/// Everything [`Game::unmake_move`] needs to restore the position before a
+/// move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Undo {
+    /// The move that was played.
+    pub mov: Move,
+    /// The piece that was captured, if any.
+    pub captured: Option<Piece>,
+    /// Castling rights before the move.
+    pub castle: [[bool; 2]; 2],
+    /// The pawn that could be captured en passant before the move.
+    pub en_passant: Option<Square>,
+    /// The halfmove clock before the move.
+    pub halfmove: usize,
+    /// The fullmove number before the move.
+    pub counter: usize,
+    /// The [`Game::position_key`] before the move.
+    pub key: u64,
+}
+
+impl fmt::Display for Move {
+    /// Writes the move in long algebraic notation, such as `e7e8q`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from, self.to)?;
+        if let Some(p) = self.promotion {
+            write!(f, "{}", p.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the king and rook of colour `c` stand after castling to `side`,
+/// where side 0 is the king side. These are the same in Chess960.
+pub(crate) fn castled_squares(c: Color, side: usize) -> (Square, Square) {
+    let (king, rook) = if side == 0 {
+        (File::G, File::F)
+    } else {
+        (File::C, File::D)
+    };
+    (
+        Square::new(king, c.back_rank()),
+        Square::new(rook, c.back_rank()),
+    )
+}
+
+impl Move {
+    /// The side a castling move castles to, 0 for the king side. The target
+    /// lies towards the rook in both standard and Chess960 notation.
+    pub(crate) fn castling_side(&self) -> usize {
+        if self.to.file() > self.from.file() {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Game {
+    /// The square the rook of colour `c` castling to `side` starts on.
+    pub(crate) fn castling_rook(&self, c: Color, side: usize) -> Square {
+        Square::new(self.castling_rooks[c.index()][side], c.back_rank())
+    }
+
+    /// The target square of castling to `side`: the rook's square in
+    /// Chess960 and the king's destination otherwise.
+    pub(crate) fn castling_target(&self, c: Color, side: usize) -> Square {
+        if self.chess960 {
+            self.castling_rook(c, side)
+        } else {
+            castled_squares(c, side).0
+        }
+    }
+
+    /// The side moving the king on `i` to `f` castles to, if it is a castling
+    /// move. In Chess960 the king takes its own castling rook; otherwise it
+    /// moves two squares from the e-file.
+    pub(crate) fn castling_side(&self, i: Square, f: Square) -> Option<usize> {
+        let piece = self.piece(i)?;
+        let y = piece.color.back_rank();
+        if piece.kind != PieceKind::King || i.rank() != y || f.rank() != y {
+            return None;
+        }
+        if self.chess960 {
+            let rook = Some(Piece::new(PieceKind::Rook, piece.color));
+            return (0..2)
+                .find(|&side| f == self.castling_rook(piece.color, side) && self.piece(f) == rook);
+        }
+        match (i.file(), f.file()) {
+            (File::E, File::G) => Some(0),
+            (File::E, File::C) => Some(1),
+            _ => None,
+        }
+    }
+
+    /// The castling right, as a colour and side, whose rook starts on
+    /// `square`.
+    fn castling_right(&self, square: Square) -> Option<(Color, usize)> {
+        Color::ALL.into_iter().find_map(|c| {
+            (0..2)
+                .find(|&side| self.castling_rook(c, side) == square)
+                .map(|side| (c, side))
+        })
+    }
+
+    /// Describes moving the piece on `i` to `f` in this position without
+    /// checking whether the move is legal.
+    pub(crate) fn describe_move(&self, i: Square, f: Square, promotion: Option<PieceKind>) -> Move {
+        let kind = self.kind(i);
+        let target = self.piece(f);
+        let pawn = kind == Some(PieceKind::Pawn);
+        let en_passant = pawn && i.file() != f.file() && target.is_none();
+
+        if let (Some(side), Some(piece)) = (self.castling_side(i, f), self.piece(i)) {
+            return Move {
+                from: i,
+                to: self.castling_target(piece.color, side),
+                promotion,
+                capture: false,
+                castle: true,
+                en_passant: false,
+                double_push: false,
+            };
+        }
+
+        Move {
+            from: i,
+            to: f,
+            promotion,
+            capture: target.is_some() || en_passant,
+            castle: false,
+            en_passant,
+            double_push: pawn && i.rank().distance(f.rank()) == 2,
+        }
+    }
+
+    /// Finds the legal move from `i` to `f`, promoting to `promotion` if a
+    /// pawn reaches the last rank.
+    pub fn find_move(&self, i: Square, f: Square, promotion: Option<PieceKind>) -> Option<Move> {
+        match self.piece(i) {
+            Some(piece) if piece.color == self.turn => {}
+            _ => return None,
+        }
+        self.get_valid_moves(i).into_iter().find(|m| {
+            m.to == f
+                && (m.promotion.is_none() || m.promotion == promotion.or(Some(self.promotion)))
+        })
+    }
+
+    /// Plays a move for the side to move if [`Game::is_legal`] accepts it,
+    /// returning what is needed to take it back with [`Game::unmake_move`].
+    pub fn make_move(&mut self, mov: Move) -> Result<Undo, MoveError> {
+        self.is_legal(mov)?;
+        Ok(self.apply(mov))
+    }
+
+    /// Plays a move known to be legal, updating castling rights, the en
+    /// passant square, the halfmove clock and the fullmove number.
+    ///
+    /// Castling rights are lost when the king moves and when a rook leaves
+    /// or is captured on its home square.
+    pub(crate) fn apply(&mut self, mov: Move) -> Undo {
+        let Move { from: i, to: f, .. } = mov;
+        let piece = self.piece(i);
+        let c = self.turn;
+
+        let undo = Undo {
+            mov,
+            captured: if mov.castle {
+                None
+            } else if mov.en_passant {
+                self.piece(Square::new(f.file(), i.rank()))
+            } else {
+                self.piece(f)
+            },
+            castle: self.castle,
+            en_passant: self.en_passant,
+            halfmove: self.halfmove,
+            counter: self.counter,
+            key: self.key,
+        };
+        self.key ^= self.state_key();
+
+        if mov.castle {
+            // The king and rook may land on each other's squares, so lift
+            // both before putting them down.
+            let side = mov.castling_side();
+            let (king_to, rook_to) = castled_squares(c, side);
+            self.set(i, None);
+            self.set(self.castling_rook(c, side), None);
+            self.set(king_to, piece);
+            self.set(rook_to, Some(Piece::new(PieceKind::Rook, c)));
+        } else {
+            self.set(i, None);
+            self.set(
+                f,
+                match mov.promotion {
+                    Some(p) => Some(Piece::new(p, c)),
+                    None => piece,
+                },
+            );
+        }
+
+        if mov.en_passant {
+            self.set(Square::new(f.file(), i.rank()), None);
+        }
+
+        if piece.is_some_and(|piece| piece.kind == PieceKind::King) {
+            self.castle[c.index()] = [false; 2];
+        }
+        // A rook leaving its home square or captured on it takes the right
+        // with it.
+        for square in [i, f] {
+            if let Some((colour, side)) = self.castling_right(square) {
+                self.castle[colour.index()][side] = false;
+            }
+        }
+
+        self.en_passant = mov.double_push.then_some(f);
+
+        if piece.is_some_and(|piece| piece.kind == PieceKind::Pawn) || mov.capture {
+            self.halfmove = 0;
+        } else {
+            self.halfmove += 1;
+        }
+
+        if c == Color::Black {
+            self.counter += 1;
+        }
+        self.turn = !c;
+        self.key ^= self.state_key();
+        self.draw_claimed = false;
+        self.history.push(undo);
+        undo
+    }
+
+    /// Takes back the last move played, restoring the exact position before
+    /// it was made. `undo` must be the value returned when that move was
+    /// played.
+    pub fn unmake_move(&mut self, undo: Undo) {
+        let Move { from: i, to: f, .. } = undo.mov;
+        let c = !self.turn;
+
+        if undo.mov.castle {
+            let side = undo.mov.castling_side();
+            let (king_to, rook_to) = castled_squares(c, side);
+            self.set(king_to, None);
+            self.set(rook_to, None);
+            self.set(i, Some(Piece::new(PieceKind::King, c)));
+            self.set(
+                self.castling_rook(c, side),
+                Some(Piece::new(PieceKind::Rook, c)),
+            );
+        } else {
+            let piece = self.piece(f);
+            self.set(
+                i,
+                if undo.mov.promotion.is_some() {
+                    Some(Piece::new(PieceKind::Pawn, c))
+                } else {
+                    piece
+                },
+            );
+
+            if undo.mov.en_passant {
+                self.set(f, None);
+                self.set(Square::new(f.file(), i.rank()), undo.captured);
+            } else {
+                self.set(f, undo.captured);
+            }
+        }
+
+        self.castle = undo.castle;
+        self.en_passant = undo.en_passant;
+        self.halfmove = undo.halfmove;
+        self.counter = undo.counter;
+        self.turn = c;
+        self.key = undo.key;
+        self.draw_claimed = false;
+        self.history.pop();
+    }
+
+    /// Takes back the last move played, if any, and returns it.
+    pub fn takeback(&mut self) -> Option<Move> {
+        let undo = *self.history.last()?;
+        self.unmake_move(undo);
+        Some(undo.mov)
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{Game, Move};
+
+impl Game {
+    /// Counts the leaf nodes of the legal move tree `depth` plies deep.
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        if depth == 1 {
+            return self.legal_moves().count() as u64;
+        }
+        let mut nodes = 0;
+        for mov in self.legal_moves().collect::<Vec<_>>() {
+            let undo = self.apply(mov);
+            nodes += self.perft(depth - 1);
+            self.unmake_move(undo);
+        }
+        nodes
+    }
+
+    /// Splits [`Game::perft`] by root move, giving the node count below
+    /// each legal move of the side to move.
+    pub fn perft_divide(&mut self, depth: usize) -> Vec<(Move, u64)> {
+        let mut divide = Vec::new();
+        for mov in self.legal_moves().collect::<Vec<_>>() {
+            let undo = self.apply(mov);
+            divide.push((mov, self.perft(depth.saturating_sub(1))));
+            self.unmake_move(undo);
+        }
+        divide
+    }
+}
@@ -0,0 +1,394 @@
+use crate::fen::START_FEN;
+use crate::{Color, Game, State};
+
+/// The Seven Tag Roster written at the top of an exported game.
+///
+/// The `Result` tag is not part of this struct because it is derived from
+/// the position when the game is exported.
+#[derive(Clone, Debug)]
+pub struct PgnTags {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+}
+
+impl Default for PgnTags {
+    fn default() -> Self {
+        Self {
+            event: "?".to_string(),
+            site: "?".to_string(),
+            date: "????.??.??".to_string(),
+            round: "?".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+        }
+    }
+}
+
+fn write_tag(pgn: &mut String, name: &str, value: &str) {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+}
+
+impl Game {
+    /// The position the recorded move list starts from.
+    fn start_position(&self) -> Game {
+        let mut game = match &self.start_fen {
+            Some(fen) => Game::from_fen(fen).unwrap(),
+            None => {
+                let mut game = Game::new();
+                game.init();
+                game
+            }
+        };
+        game.chess960 = self.chess960;
+        game
+    }
+
+    /// Every move played so far in Standard Algebraic Notation.
+    pub fn san_moves(&self) -> Vec<String> {
+        let mut game = self.start_position();
+        let mut sans = Vec::new();
+
+        for mov in self.moves() {
+            sans.push(game.san(mov));
+            game.apply(mov);
+        }
+
+        sans
+    }
+
+    /// Move numbers and SAN moves, one token each.
+    fn movetext(&self) -> Vec<String> {
+        let start = self.start_position();
+        let mut tokens = Vec::new();
+        let mut counter = start.counter;
+        let mut turn = start.turn;
+        for (n, san) in self.san_moves().into_iter().enumerate() {
+            if turn == Color::White {
+                tokens.push(format!("{}.", counter));
+            } else if n == 0 {
+                tokens.push(format!("{}...", counter));
+            }
+            tokens.push(san);
+            if turn == Color::Black {
+                counter += 1;
+            }
+            turn = !turn;
+        }
+        tokens
+    }
+
+    /// The numbered move list played so far, such as `1. e4 e5 2. Nf3`.
+    pub fn move_list(&self) -> String {
+        self.movetext().join(" ")
+    }
+
+    /// The PGN result token for the current position: `1-0`, `0-1`,
+    /// `1/2-1/2` or `*` while the game is still in progress. A draw that may
+    /// be claimed counts only once it has been.
+    pub fn result(&self) -> &'static str {
+        if self.draw_claimed {
+            return "1/2-1/2";
+        }
+        match self.check_game_end() {
+            State::WhiteCheckmate => "0-1",
+            State::BlackCheckmate => "1-0",
+            State::WhiteStalemate
+            | State::BlackStalemate
+            | State::DeadPosition
+            | State::FivefoldRepetition
+            | State::SeventyFiveMoveRule => "1/2-1/2",
+            State::ThreefoldRepetition
+            | State::FiftyMoveRule
+            | State::WhiteCheck
+            | State::BlackCheck
+            | State::Continue => "*",
+        }
+    }
+
+    /// Exports the game played so far as PGN, with the moves in SAN and the
+    /// result taken from the current position.
+    pub fn to_pgn(&self, tags: &PgnTags) -> String {
+        let result = self.result();
+        let mut pgn = String::new();
+
+        write_tag(&mut pgn, "Event", &tags.event);
+        write_tag(&mut pgn, "Site", &tags.site);
+        write_tag(&mut pgn, "Date", &tags.date);
+        write_tag(&mut pgn, "Round", &tags.round);
+        write_tag(&mut pgn, "White", &tags.white);
+        write_tag(&mut pgn, "Black", &tags.black);
+        write_tag(&mut pgn, "Result", result);
+
+        if self.chess960 {
+            write_tag(&mut pgn, "Variant", "Chess960");
+        }
+
+        if let Some(fen) = &self.start_fen
+            && fen != START_FEN
+        {
+            write_tag(&mut pgn, "SetUp", "1");
+            write_tag(&mut pgn, "FEN", fen);
+        }
+        pgn.push('\n');
+
+        let mut tokens = self.movetext();
+        tokens.push(result.to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > 80 {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        pgn.push_str(&line);
+        pgn.push('\n');
+
+        pgn
+    }
+}
+
+/// A game read from PGN and replayed through move validation.
+#[derive(Clone)]
+pub struct PgnGame {
+    /// Every tag pair in the order it appeared.
+    pub tags: Vec<(String, String)>,
+    /// The game termination marker, such as `1-0` or `*`.
+    pub result: String,
+    /// The position after the last move of the main line.
+    pub game: Game,
+}
+
+impl PgnGame {
+    /// Returns the value of the tag called `name`, if present.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+enum Token {
+    Tag(String, String),
+    Move(String),
+    Result(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line_start = true;
+    let mut depth = 0;
+
+    while let Some(ch) = chars.next() {
+        if ch == '\n' {
+            line_start = true;
+            continue;
+        }
+        if ch.is_whitespace() {
+            continue;
+        }
+        let at_line_start = line_start;
+        line_start = false;
+
+        match ch {
+            '%' if at_line_start => {
+                for ch in chars.by_ref() {
+                    if ch == '\n' {
+                        line_start = true;
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                for ch in chars.by_ref() {
+                    if ch == '\n' {
+                        line_start = true;
+                        break;
+                    }
+                }
+            }
+            '{' => {
+                if !chars.by_ref().any(|ch| ch == '}') {
+                    return Err("Unterminated comment".to_string());
+                }
+            }
+            '(' => depth += 1,
+            ')' => {
+                if depth == 0 {
+                    return Err("Unmatched ')' closing a variation".to_string());
+                }
+                depth -= 1;
+            }
+            '$' => while chars.next_if(|c| c.is_ascii_digit()).is_some() {},
+            '[' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                    name.push(c);
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if chars.next() != Some('"') {
+                    return Err(format!("Tag '{}' has no quoted value", name));
+                }
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => value.extend(chars.next()),
+                        Some('"') => break,
+                        Some(c) => value.push(c),
+                        None => return Err(format!("Unterminated value for tag '{}'", name)),
+                    }
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if chars.next() != Some(']') {
+                    return Err(format!("Tag '{}' is missing its closing ']'", name));
+                }
+                if depth == 0 {
+                    tokens.push(Token::Tag(name, value));
+                }
+            }
+            _ => {
+                let mut symbol = ch.to_string();
+                while let Some(c) =
+                    chars.next_if(|c| !c.is_whitespace() && !"{}()[];$".contains(*c))
+                {
+                    symbol.push(c);
+                }
+                if depth > 0 {
+                    continue;
+                }
+                if matches!(symbol.as_str(), "1-0" | "0-1" | "1/2-1/2" | "*") {
+                    tokens.push(Token::Result(symbol));
+                    continue;
+                }
+                // Move numbers may be followed by any number of periods,
+                // including none.
+                let number = symbol.trim_start_matches(|c: char| c.is_ascii_digit());
+                let symbol = if number.len() < symbol.len()
+                    && (number.is_empty() || number.starts_with('.'))
+                {
+                    number.trim_start_matches('.')
+                } else {
+                    symbol.trim_start_matches('.')
+                };
+                if !symbol.is_empty() {
+                    tokens.push(Token::Move(symbol.to_string()));
+                }
+            }
+        }
+    }
+
+    if depth > 0 {
+        return Err("Unterminated variation".to_string());
+    }
+
+    Ok(tokens)
+}
+
+fn replay(
+    number: usize,
+    tags: Vec<(String, String)>,
+    moves: Vec<String>,
+    result: String,
+) -> Result<PgnGame, String> {
+    let mut game = match tags.iter().find(|(n, _)| n == "FEN") {
+        Some((_, fen)) => {
+            Game::from_fen(fen).map_err(|e| format!("Game {}: invalid FEN tag: {}", number, e))?
+        }
+        None => {
+            let mut game = Game::new();
+            game.init();
+            game
+        }
+    };
+    if let Some((_, variant)) = tags.iter().find(|(n, _)| n == "Variant") {
+        match variant.to_ascii_lowercase().as_str() {
+            "chess960" | "fischerandom" | "fischer random" => game.chess960 = true,
+            "standard" => {}
+            _ => {
+                return Err(format!(
+                    "Game {}: unsupported variant '{}'",
+                    number, variant
+                ));
+            }
+        }
+    }
+
+    for (ply, san) in moves.iter().enumerate() {
+        let label = format!(
+            "{}{} {}",
+            game.counter,
+            if game.turn == Color::White {
+                "."
+            } else {
+                "..."
+            },
+            san
+        );
+        game.move_san(san)
+            .map_err(|e| format!("Game {}, ply {} ({}): {}", number, ply + 1, label, e))?;
+    }
+
+    Ok(PgnGame { tags, result, game })
+}
+
+/// Reads every game in a PGN document and replays its main line.
+///
+/// Comments, NAGs and variations are skipped. The first illegal, ambiguous
+/// or malformed move stops parsing with an error naming the game, the ply
+/// and the move.
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, String> {
+    let mut games = Vec::new();
+    let mut tags = Vec::new();
+    let mut moves = Vec::new();
+
+    for token in tokenize(text)? {
+        match token {
+            Token::Tag(name, value) => {
+                if !moves.is_empty() {
+                    let game = replay(games.len() + 1, tags, moves, "*".to_string())?;
+                    games.push(game);
+                    tags = Vec::new();
+                    moves = Vec::new();
+                }
+                tags.push((name, value));
+            }
+            Token::Move(san) => moves.push(san),
+            Token::Result(result) => {
+                let game = replay(games.len() + 1, tags, moves, result)?;
+                games.push(game);
+                tags = Vec::new();
+                moves = Vec::new();
+            }
+        }
+    }
+
+    if !tags.is_empty() || !moves.is_empty() {
+        games.push(replay(games.len() + 1, tags, moves, "*".to_string())?);
+    }
+
+    Ok(games)
+}
+
+impl Game {
+    /// Loads the first game of a PGN document, returning the final position
+    /// with its move list.
+    pub fn from_pgn(text: &str) -> Result<Self, String> {
+        parse_pgn(text)?
+            .into_iter()
+            .next()
+            .map(|pgn| pgn.game)
+            .ok_or_else(|| "PGN contains no games".to_string())
+    }
+}
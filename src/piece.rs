@@ -0,0 +1,133 @@
+use crate::Color;
+use std::fmt;
+
+/// The kind of a chess piece, without its colour.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+/// Lowercase FEN letters, indexed by [`PieceKind::index`].
+const LETTERS: [char; 6] = ['p', 'n', 'b', 'r', 'q', 'k'];
+
+/// Unicode glyphs for white then black pieces, indexed by
+/// [`PieceKind::index`].
+const GLYPHS: [[char; 6]; 2] = [
+    ['♙', '♘', '♗', '♖', '♕', '♔'],
+    ['♟', '♞', '♝', '♜', '♛', '♚'],
+];
+
+/// Material values the AI assigns, with a pawn worth 10, indexed by
+/// [`PieceKind::index`].
+const VALUES: [isize; 6] = [10, 30, 30, 50, 90, 900];
+
+impl PieceKind {
+    /// Every kind from pawn to king, the order pieces are tried as
+    /// recapturers.
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    /// The kinds a pawn may promote to, strongest first.
+    pub const PROMOTIONS: [PieceKind; 4] = [
+        PieceKind::Queen,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Knight,
+    ];
+
+    /// 0 for a pawn up to 5 for a king, for indexing tables kept per kind.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The material value the AI assigns to the kind, with a pawn worth 10.
+    pub const fn value(self) -> isize {
+        VALUES[self.index()]
+    }
+
+    /// Parses a piece letter in either case, such as `n` or `N`.
+    pub fn from_char(c: char) -> Option<PieceKind> {
+        let c = c.to_ascii_lowercase();
+        LETTERS
+            .iter()
+            .position(|&letter| letter == c)
+            .map(|i| PieceKind::ALL[i])
+    }
+
+    /// Parses a promotion letter in either case: `q`, `r`, `b` or `n`.
+    pub fn from_promotion_char(c: char) -> Option<PieceKind> {
+        PieceKind::from_char(c).filter(|kind| PieceKind::PROMOTIONS.contains(kind))
+    }
+
+    /// The lowercase piece letter, such as `n`.
+    pub const fn to_char(self) -> char {
+        LETTERS[self.index()]
+    }
+}
+
+impl fmt::Display for PieceKind {
+    /// Writes the uppercase letter used in SAN, such as `N`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char().to_ascii_uppercase())
+    }
+}
+
+/// A piece of one colour. Empty squares are `None` wherever a square's
+/// content is an `Option<Piece>`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub kind: PieceKind,
+    pub color: Color,
+}
+
+impl Piece {
+    pub const fn new(kind: PieceKind, color: Color) -> Piece {
+        Piece { kind, color }
+    }
+
+    /// Parses a FEN letter, uppercase for white and lowercase for black.
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        Some(Piece::new(PieceKind::from_char(c)?, color))
+    }
+
+    /// The FEN letter, uppercase for white and lowercase for black.
+    pub const fn fen_char(self) -> char {
+        match self.color {
+            Color::White => self.kind.to_char().to_ascii_uppercase(),
+            Color::Black => self.kind.to_char(),
+        }
+    }
+
+    /// The Unicode chess glyph, hollow for white and filled for black.
+    pub const fn unicode(self) -> char {
+        GLYPHS[self.color.index()][self.kind.index()]
+    }
+
+    /// The material value of the piece, see [`PieceKind::value`].
+    pub const fn value(self) -> isize {
+        self.kind.value()
+    }
+}
+
+impl fmt::Display for Piece {
+    /// Writes the FEN letter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fen_char())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::Game;
+
+impl Game {
+    /// How many times the current position has occurred in this game,
+    /// including now. Only positions since the last capture or pawn move
+    /// are compared, as earlier ones cannot recur.
+    pub fn repetitions(&self) -> usize {
+        let key = self.position_key();
+        1 + self
+            .history
+            .iter()
+            .rev()
+            .take(self.halfmove)
+            .filter(|undo| undo.key == key)
+            .count()
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{File, Game, Move, MoveError, Piece, PieceKind, Rank, Square};
+
+impl Game {
+    /// Formats a move in Standard Algebraic Notation.
+    ///
+    /// The move must be legal in the current position. Pieces are
+    /// disambiguated by file, then rank, then square, and the move is
+    /// suffixed with `+` or `#` when it gives check or mate.
+    pub fn san(&mut self, mov: Move) -> String {
+        let Move { from: i, to: f, .. } = mov;
+        let kind = self.kind(i).unwrap();
+
+        let mut san = match kind {
+            _ if mov.castle && mov.castling_side() == 0 => "O-O".to_string(),
+            _ if mov.castle => "O-O-O".to_string(),
+            PieceKind::Pawn => {
+                let mut san = String::new();
+                if mov.capture {
+                    san.push(i.file().to_char());
+                    san.push('x');
+                }
+                san.push_str(&f.to_string());
+                if let Some(p) = mov.promotion {
+                    san.push('=');
+                    san.push_str(&p.to_string());
+                }
+                san
+            }
+            _ => {
+                let mut san = kind.to_string();
+
+                let others: Vec<Square> = self
+                    .legal_moves()
+                    .filter(|m| m.to == f && m.from != i && self.kind(m.from) == Some(kind))
+                    .map(|m| m.from)
+                    .collect();
+                if !others.is_empty() {
+                    if others.iter().all(|s| s.file() != i.file()) {
+                        san.push(i.file().to_char());
+                    } else if others.iter().all(|s| s.rank() != i.rank()) {
+                        san.push(i.rank().to_char());
+                    } else {
+                        san.push_str(&i.to_string());
+                    }
+                }
+
+                if mov.capture {
+                    san.push('x');
+                }
+                san.push_str(&f.to_string());
+                san
+            }
+        };
+
+        let undo = self.apply(mov);
+        let king = self.find(Piece::new(PieceKind::King, self.turn)).unwrap();
+        if self.check(king, self.turn) {
+            san.push(if self.legal_moves().next().is_none() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+        self.unmake_move(undo);
+
+        san
+    }
+
+    /// Resolves a move in Standard Algebraic Notation against the legal moves
+    /// of the side to move.
+    ///
+    /// Accepts castling as `O-O` or `0-0`, captures with or without `x`,
+    /// promotions as `e8=Q` or `e8Q`, and ignores trailing `+`, `#`, `!` and
+    /// `?`. Fails if no legal move matches or if the move is ambiguous.
+    pub fn parse_san(&self, san: &str) -> Result<Move, MoveError> {
+        let c = self.turn;
+        let text = san.trim_end_matches(['+', '#', '!', '?']);
+
+        if matches!(text, "O-O" | "0-0" | "O-O-O" | "0-0-0") {
+            let side = if text.len() == 3 { 0 } else { 1 };
+            return self
+                .legal_moves()
+                .find(|m| m.castle && m.castling_side() == side)
+                .ok_or_else(|| {
+                    self.castling_error(side)
+                        .unwrap_or_else(|| MoveError::NoMatchingMove(san.to_string()))
+                });
+        }
+
+        let mut chars: Vec<char> = text.chars().collect();
+
+        let mut promotion = None;
+        if let Some(kind) = chars
+            .last()
+            .and_then(|&last| PieceKind::from_promotion_char(last))
+            && chars.len() > 2
+        {
+            let before = chars[chars.len() - 2];
+            if before == '=' || before.is_ascii_digit() {
+                promotion = Some(kind);
+                chars.pop();
+                if before == '=' {
+                    chars.pop();
+                }
+            }
+        }
+
+        let kind = match chars.first() {
+            Some(&letter) if letter.is_ascii_uppercase() => {
+                chars.remove(0);
+                PieceKind::from_char(letter)
+                    .filter(|&kind| kind != PieceKind::Pawn)
+                    .ok_or_else(|| {
+                        MoveError::InvalidSan(format!("invalid piece '{}' in '{}'", letter, san))
+                    })?
+            }
+            _ => PieceKind::Pawn,
+        };
+
+        if chars.len() < 2 {
+            return Err(MoveError::InvalidSan(format!(
+                "missing destination square in '{}'",
+                san
+            )));
+        }
+        let f = match (
+            File::from_char(chars[chars.len() - 2]),
+            Rank::from_char(chars[chars.len() - 1]),
+        ) {
+            (Some(file), Some(rank)) => Square::new(file, rank),
+            _ => {
+                return Err(MoveError::InvalidSan(format!(
+                    "bad destination square in '{}'",
+                    san
+                )));
+            }
+        };
+        chars.truncate(chars.len() - 2);
+
+        if chars.last() == Some(&'x') {
+            chars.pop();
+        }
+        let mut from_file = None;
+        let mut from_rank = None;
+        for ch in chars {
+            match (File::from_char(ch), Rank::from_char(ch)) {
+                (Some(file), _) if from_file.is_none() => from_file = Some(file),
+                (_, Some(rank)) if from_rank.is_none() => from_rank = Some(rank),
+                _ => {
+                    return Err(MoveError::InvalidSan(format!(
+                        "unexpected '{}' in '{}'",
+                        ch, san
+                    )));
+                }
+            }
+        }
+
+        let last_rank = c.promotion_rank();
+        match (kind, promotion) {
+            (PieceKind::Pawn, None) if f.rank() == last_rank => {
+                return Err(MoveError::MissingPromotion);
+            }
+            (PieceKind::Pawn, Some(_)) if f.rank() == last_rank => {}
+            (_, Some(p)) => return Err(MoveError::InvalidPromotion(p)),
+            _ => {}
+        }
+
+        let candidates: Vec<Move> = self
+            .legal_moves()
+            .filter(|m| {
+                let i = m.from;
+                self.kind(i) == Some(kind)
+                    && from_file.is_none_or(|x| x == i.file())
+                    && from_rank.is_none_or(|y| y == i.rank())
+                    && m.to == f
+                    && m.promotion == promotion
+            })
+            .collect();
+
+        match candidates[..] {
+            [] => Err(MoveError::NoMatchingMove(san.to_string())),
+            [mov] => Ok(mov),
+            _ => Err(MoveError::Ambiguous(san.to_string())),
+        }
+    }
+
+    /// Parses a SAN move and plays it.
+    pub fn move_san(&mut self, san: &str) -> Result<(), MoveError> {
+        let mov = self.parse_san(san)?;
+        self.apply(mov);
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::bitboard;
+use crate::{Game, Move, PieceKind, Square};
+
+impl Game {
+    /// Static exchange evaluation: the material the side to move expects to
+    /// gain by playing `mov` when both sides keep recapturing on the target
+    /// square with their least valuable attacker, and either may stop when
+    /// continuing would lose material.
+    ///
+    /// Values are those of [`PieceKind::value`]. Pieces behind an attacker
+    /// join in once it has captured, but pins are ignored. Returns 0 if
+    /// there is no piece on `mov.from`.
+    pub fn see(&self, mov: Move) -> isize {
+        let Move { from: i, to: f, .. } = mov;
+        let Some(piece) = self.piece(i) else {
+            return 0;
+        };
+        let c = piece.color;
+        let mut occupied = self.bitboards.occupied & !bitboard::bit(i);
+
+        let mut gains = [0; 32];
+        gains[0] = if mov.en_passant {
+            occupied &= !bitboard::bit(Square::new(f.file(), i.rank()));
+            PieceKind::Pawn.value()
+        } else {
+            self.piece(f).map_or(0, |piece| piece.value())
+        };
+
+        let mut on_square = piece.kind;
+        if let Some(p) = mov.promotion {
+            on_square = p;
+            gains[0] += on_square.value() - PieceKind::Pawn.value();
+        }
+
+        let mut side = !c;
+        let mut depth = 0;
+        loop {
+            let attackers = self.bitboards.attackers_through(f, side, occupied);
+            let colour = side.index();
+            let Some(kind) = PieceKind::ALL
+                .into_iter()
+                .find(|kind| attackers & self.bitboards.pieces[colour][kind.index()] != 0)
+            else {
+                break;
+            };
+
+            depth += 1;
+            gains[depth] = on_square.value() - gains[depth - 1];
+            if depth == gains.len() - 1 {
+                break;
+            }
+
+            let from = attackers & self.bitboards.pieces[colour][kind.index()];
+            occupied &= !(from & from.wrapping_neg());
+            on_square = kind;
+            side = !side;
+        }
+
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+        gains[0]
+    }
+}
@@ -0,0 +1,284 @@
+use crate::ParseMoveError;
+use std::fmt;
+use std::ops::Not;
+use std::str::FromStr;
+
+/// One of the two sides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    /// Both colours, white first.
+    pub const ALL: [Color; 2] = [Color::White, Color::Black];
+
+    /// 0 for white and 1 for black, for indexing tables kept per side.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The rank this side's pieces start on.
+    pub const fn back_rank(self) -> Rank {
+        match self {
+            Color::White => Rank::FIRST,
+            Color::Black => Rank::EIGHTH,
+        }
+    }
+
+    /// The rank this side's pawns start on.
+    pub const fn pawn_rank(self) -> Rank {
+        match self {
+            Color::White => Rank::SECOND,
+            Color::Black => Rank::SEVENTH,
+        }
+    }
+
+    /// The rank this side's pawns promote on.
+    pub const fn promotion_rank(self) -> Rank {
+        match self {
+            Color::White => Rank::EIGHTH,
+            Color::Black => Rank::FIRST,
+        }
+    }
+
+    /// The direction this side's pawns advance in, in ranks.
+    pub const fn forward(self) -> isize {
+        match self {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+}
+
+impl Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Color::White => "White",
+            Color::Black => "Black",
+        })
+    }
+}
+
+/// A column of the board, `a` to `h`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct File(u8);
+
+impl File {
+    pub const A: File = File(0);
+    pub const B: File = File(1);
+    pub const C: File = File(2);
+    pub const D: File = File(3);
+    pub const E: File = File(4);
+    pub const F: File = File(5);
+    pub const G: File = File(6);
+    pub const H: File = File(7);
+
+    /// The file with index `index`, counting from 0 for `a`.
+    pub const fn new(index: usize) -> Option<File> {
+        if index < 8 {
+            Some(File(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// The index of the file, 0 for `a` up to 7 for `h`.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Parses a file letter, `a` to `h`.
+    pub fn from_char(c: char) -> Option<File> {
+        match c {
+            'a'..='h' => Some(File(c as u8 - b'a')),
+            _ => None,
+        }
+    }
+
+    /// The file letter, `a` to `h`.
+    pub const fn to_char(self) -> char {
+        (b'a' + self.0) as char
+    }
+
+    /// Every file from `a` to `h`.
+    pub fn all() -> impl Iterator<Item = File> {
+        (0..8).map(File)
+    }
+
+    /// The file on the other side of the board, so `a` becomes `h`.
+    pub const fn mirror(self) -> File {
+        File(7 - self.0)
+    }
+
+    /// How many files apart the two are.
+    pub const fn distance(self, other: File) -> usize {
+        self.0.abs_diff(other.0) as usize
+    }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// A row of the board, `1` to `8`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Rank(u8);
+
+impl Rank {
+    pub const FIRST: Rank = Rank(0);
+    pub const SECOND: Rank = Rank(1);
+    pub const THIRD: Rank = Rank(2);
+    pub const FOURTH: Rank = Rank(3);
+    pub const FIFTH: Rank = Rank(4);
+    pub const SIXTH: Rank = Rank(5);
+    pub const SEVENTH: Rank = Rank(6);
+    pub const EIGHTH: Rank = Rank(7);
+
+    /// The rank with index `index`, counting from 0 for the first rank.
+    pub const fn new(index: usize) -> Option<Rank> {
+        if index < 8 {
+            Some(Rank(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// The index of the rank, 0 for the first up to 7 for the eighth.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Parses a rank digit, `1` to `8`.
+    pub fn from_char(c: char) -> Option<Rank> {
+        match c {
+            '1'..='8' => Some(Rank(c as u8 - b'1')),
+            _ => None,
+        }
+    }
+
+    /// The rank digit, `1` to `8`.
+    pub const fn to_char(self) -> char {
+        (b'1' + self.0) as char
+    }
+
+    /// Every rank from the first to the eighth.
+    pub fn all() -> impl Iterator<Item = Rank> {
+        (0..8).map(Rank)
+    }
+
+    /// The rank as seen from the other side, so the first becomes the
+    /// eighth.
+    pub const fn mirror(self) -> Rank {
+        Rank(7 - self.0)
+    }
+
+    /// How many ranks apart the two are.
+    pub const fn distance(self, other: Rank) -> usize {
+        self.0.abs_diff(other.0) as usize
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// A square of the board, such as `e4`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square(u8);
+
+impl Square {
+    /// The square on `file` and `rank`.
+    pub const fn new(file: File, rank: Rank) -> Square {
+        Square(rank.0 * 8 + file.0)
+    }
+
+    /// The square with index `rank * 8 + file`, so 0 is a1 and 63 is h8.
+    pub const fn from_index(index: usize) -> Option<Square> {
+        if index < 64 {
+            Some(Square(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// The square for a bit index below 64.
+    pub(crate) const fn from_bit(index: u32) -> Square {
+        Square(index as u8)
+    }
+
+    /// The index of the square, `rank * 8 + file`.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub const fn file(self) -> File {
+        File(self.0 % 8)
+    }
+
+    pub const fn rank(self) -> Rank {
+        Rank(self.0 / 8)
+    }
+
+    /// Every square from a1 to h8, rank by rank.
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+
+    /// The square as seen from the other side, so e2 becomes e7.
+    pub const fn mirror(self) -> Square {
+        Square::new(self.file(), self.rank().mirror())
+    }
+
+    /// The number of king moves between the two squares.
+    pub const fn distance(self, other: Square) -> usize {
+        let files = self.file().distance(other.file());
+        let ranks = self.rank().distance(other.rank());
+        if files > ranks { files } else { ranks }
+    }
+
+    /// The square `files` to the right and `ranks` up from this one, if it
+    /// is on the board.
+    pub fn offset(self, files: isize, ranks: isize) -> Option<Square> {
+        let file = File::new(self.file().index().checked_add_signed(files)?)?;
+        let rank = Rank::new(self.rank().index().checked_add_signed(ranks)?)?;
+        Some(Square::new(file, rank))
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+impl FromStr for Square {
+    type Err = ParseMoveError;
+
+    /// Parses a square name such as `e4`.
+    fn from_str(s: &str) -> Result<Square, ParseMoveError> {
+        let chars: Vec<char> = s.chars().collect();
+        let [file, rank] = chars[..] else {
+            return Err(ParseMoveError::InvalidLength(chars.len()));
+        };
+        let file = File::from_char(file).ok_or(ParseMoveError::InvalidFile(file))?;
+        let rank = Rank::from_char(rank).ok_or(ParseMoveError::InvalidRank(rank))?;
+        Ok(Square::new(file, rank))
+    }
+}
@@ -0,0 +1,44 @@
+/// The status of a game as seen from the side that is checked or mated.
+#[derive(Copy, Clone)]
+pub enum State {
+    WhiteCheckmate,
+    BlackCheckmate,
+    WhiteStalemate,
+    BlackStalemate,
+    WhiteCheck,
+    BlackCheck,
+    Continue,
+    /// Neither side has enough material left to checkmate.
+    DeadPosition,
+    /// The position has occurred three times; either side may claim a draw.
+    ThreefoldRepetition,
+    /// The position has occurred five times and the game is drawn.
+    FivefoldRepetition,
+    /// Fifty moves by each side without a capture or pawn move; either side
+    /// may claim a draw.
+    FiftyMoveRule,
+    /// Seventy-five moves by each side without a capture or pawn move and
+    /// the game is drawn.
+    SeventyFiveMoveRule,
+}
+
+impl State {
+    /// A human readable description of the state.
+    pub fn symbol(&self) -> String {
+        (match self {
+            State::WhiteCheckmate => "White is checkmated",
+            State::BlackCheckmate => "Black is checkmated",
+            State::WhiteStalemate => "White is stalemated",
+            State::BlackStalemate => "Black is stalemated",
+            State::WhiteCheck => "White is in check",
+            State::BlackCheck => "Black is in check",
+            State::Continue => "Game continues",
+            State::DeadPosition => "Draw by insufficient material",
+            State::ThreefoldRepetition => "Draw by threefold repetition",
+            State::FivefoldRepetition => "Draw by fivefold repetition",
+            State::FiftyMoveRule => "Draw by the fifty-move rule",
+            State::SeventyFiveMoveRule => "Draw by the seventy-five-move rule",
+        })
+        .to_string()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{File, Game, MoveError, PieceKind, Rank, Square};
+use std::fmt;
+
+/// Why a long algebraic move such as `e7e8q` could not be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMoveError {
+    /// The move or square had the wrong number of characters.
+    InvalidLength(usize),
+    /// A square's file was not in `a`-`h`.
+    InvalidFile(char),
+    /// A square's rank was not in `1`-`8`.
+    InvalidRank(char),
+    /// The promotion suffix was not one of `q`, `r`, `b` or `n`.
+    InvalidPromotion(char),
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoveError::InvalidLength(len) => {
+                write!(
+                    f,
+                    "Invalid length {}, expected a square like 'e4' or a move like 'e2e4'",
+                    len
+                )
+            }
+            ParseMoveError::InvalidFile(c) => write!(f, "Invalid file '{}', expected a-h", c),
+            ParseMoveError::InvalidRank(c) => write!(f, "Invalid rank '{}', expected 1-8", c),
+            ParseMoveError::InvalidPromotion(c) => {
+                write!(f, "Invalid promotion '{}', expected q, r, b or n", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+fn parse_coords(file: char, rank: char) -> Result<Square, ParseMoveError> {
+    let file = File::from_char(file).ok_or(ParseMoveError::InvalidFile(file))?;
+    let rank = Rank::from_char(rank).ok_or(ParseMoveError::InvalidRank(rank))?;
+    Ok(Square::new(file, rank))
+}
+
+impl Game {
+    /// Parses a long algebraic move such as `"e2e4"` or `"e7e8q"` into
+    /// `[from, to]` squares and the promotion piece, if any.
+    pub fn parse_move(mov: &str) -> Result<([Square; 2], Option<PieceKind>), ParseMoveError> {
+        let chars: Vec<char> = mov.to_lowercase().chars().collect();
+        if chars.len() != 4 && chars.len() != 5 {
+            return Err(ParseMoveError::InvalidLength(chars.len()));
+        }
+
+        let i = parse_coords(chars[0], chars[1])?;
+        let f = parse_coords(chars[2], chars[3])?;
+        let promotion = chars
+            .get(4)
+            .map(|&c| PieceKind::from_promotion_char(c).ok_or(ParseMoveError::InvalidPromotion(c)))
+            .transpose()?;
+
+        Ok(([i, f], promotion))
+    }
+
+    /// Parses a long algebraic move and plays it, promoting to the piece
+    /// given in the move or to the current [`Game::promotion`] otherwise.
+    ///
+    /// A promotion suffix on a move that does not promote is rejected.
+    pub fn move_uci(&mut self, mov: &str) -> Result<(), MoveError> {
+        let ([i, f], promotion) = Game::parse_move(mov)?;
+        let promotion = promotion.or_else(|| self.default_promotion(i, f));
+        self.make_move(self.describe_move(i, f, promotion))?;
+        Ok(())
+    }
+}
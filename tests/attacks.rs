@@ -0,0 +1,85 @@
+//! Attackers of a square and the squares each side attacks.
+
+use chess::{Bitboard, Color, Game, Square};
+
+fn square(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+/// The squares in `bb` from a1 to h8.
+fn names(bb: Bitboard) -> Vec<String> {
+    Square::all()
+        .filter(|sq| bb & 1 << sq.index() != 0)
+        .map(|sq| sq.to_string())
+        .collect()
+}
+
+fn attackers(fen: &str, target: &str, c: Color) -> Vec<String> {
+    let game = Game::from_fen(fen).unwrap();
+    names(game.attackers_of(square(target), c))
+}
+
+fn attack_map(fen: &str, c: Color) -> Vec<String> {
+    names(Game::from_fen(fen).unwrap().attack_map(c))
+}
+
+#[test]
+fn pawns_attack_forwards_for_their_side() {
+    let fen = "4k3/8/8/8/3Pp3/8/8/4K3 w - - 0 1";
+    assert_eq!(attackers(fen, "c5", Color::White), ["d4"]);
+    assert_eq!(attackers(fen, "e5", Color::White), ["d4"]);
+    assert!(attackers(fen, "c3", Color::White).is_empty());
+    assert_eq!(attackers(fen, "d3", Color::Black), ["e4"]);
+    assert_eq!(attackers(fen, "f3", Color::Black), ["e4"]);
+    assert!(attackers(fen, "f5", Color::Black).is_empty());
+    // Neither pawn attacks the square in front of it.
+    assert!(attackers(fen, "d5", Color::White).is_empty());
+    assert!(attackers(fen, "e3", Color::Black).is_empty());
+}
+
+#[test]
+fn sliders_stop_at_the_first_piece() {
+    let battery = "4k3/8/8/8/8/8/Q7/R3K3 w - - 0 1";
+    assert_eq!(attackers(battery, "a5", Color::White), ["a2"]);
+    assert_eq!(attackers(battery, "a2", Color::White), ["a1"]);
+
+    let blocked = "4k3/8/8/8/8/2p5/8/B3K3 w - - 0 1";
+    assert_eq!(attackers(blocked, "b2", Color::White), ["a1"]);
+    assert_eq!(attackers(blocked, "c3", Color::White), ["a1"]);
+    assert!(attackers(blocked, "d4", Color::White).is_empty());
+    assert!(attackers(blocked, "e5", Color::White).is_empty());
+}
+
+#[test]
+fn knights_and_kings() {
+    let fen = "4k3/8/8/8/8/8/8/1N2K3 w - - 0 1";
+    assert_eq!(attackers(fen, "d2", Color::White), ["b1", "e1"]);
+    assert_eq!(attackers(fen, "a3", Color::White), ["b1"]);
+    assert_eq!(attackers(fen, "f2", Color::White), ["e1"]);
+    assert!(attackers(fen, "b3", Color::White).is_empty());
+    assert_eq!(attackers(fen, "d7", Color::Black), ["e8"]);
+    assert!(attackers(fen, "d2", Color::Black).is_empty());
+}
+
+#[test]
+fn attack_map_includes_defended_pieces() {
+    assert_eq!(
+        attack_map("4k3/8/8/8/8/8/3P4/1N2K3 w - - 0 1", Color::White),
+        ["d1", "f1", "d2", "e2", "f2", "a3", "c3", "e3"]
+    );
+    assert_eq!(
+        attack_map("4k3/4p3/8/8/8/8/8/4K3 w - - 0 1", Color::Black),
+        ["d6", "f6", "d7", "e7", "f7", "d8", "f8"]
+    );
+}
+
+#[test]
+fn attack_map_stops_sliders_at_blockers() {
+    assert_eq!(
+        attack_map("4k3/8/8/8/8/8/8/R1N1K3 w - - 0 1", Color::White),
+        [
+            "b1", "c1", "d1", "f1", "a2", "d2", "e2", "f2", "a3", "b3", "d3", "a4", "a5", "a6",
+            "a7", "a8"
+        ]
+    );
+}
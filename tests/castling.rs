@@ -0,0 +1,96 @@
+//! Castling rights after rook captures and rejected moves, checked against
+//! positions from the perft suite.
+
+use chess::{Game, MoveError, Square};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const ROOKS: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+fn square(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+/// Plays `moves` in SAN and checks the resulting FEN, and that the
+/// incrementally updated key matches one computed from that FEN.
+fn assert_after(fen: &str, moves: &[&str], expected: &str) {
+    let mut game = Game::from_fen(fen).unwrap();
+    for san in moves {
+        game.move_san(san).unwrap();
+    }
+    assert_eq!(game.to_fen(), expected);
+    assert_eq!(
+        game.position_key(),
+        Game::from_fen(expected).unwrap().position_key()
+    );
+}
+
+/// Checks that every position within `depth` plies writes a FEN that loads
+/// back to the same key, which fails if a right survives its rook.
+fn assert_rights_consistent(game: &mut Game, depth: usize) {
+    let fen = game.to_fen();
+    let reloaded = Game::from_fen(&fen).unwrap_or_else(|e| panic!("{}: {}", fen, e));
+    assert_eq!(reloaded.position_key(), game.position_key(), "{}", fen);
+    if depth == 0 {
+        return;
+    }
+    for mov in game.legal_moves().collect::<Vec<_>>() {
+        let undo = game.make_move(mov).unwrap();
+        assert_rights_consistent(game, depth - 1);
+        game.unmake_move(undo);
+    }
+}
+
+#[test]
+fn rook_captured_on_home_square() {
+    assert_after(ROOKS, &["Rxa8+"], "R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 1");
+    assert_after(ROOKS, &["Rxh8+"], "r3k2R/8/8/8/8/8/8/R3K3 b Qq - 0 1");
+}
+
+#[test]
+fn rook_captured_by_promotion() {
+    assert_after(
+        KIWIPETE,
+        &["a3", "hxg2", "a4", "gxh1=Q"],
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q2/1PPBBP1P/R3K2q w Qkq - 0 3",
+    );
+}
+
+#[test]
+fn rook_captured_by_knight() {
+    assert_after(
+        POSITION_5,
+        &["a3", "Nxh1"],
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/P7/1PP1N1PP/RNBQK2n w Q - 0 9",
+    );
+}
+
+#[test]
+fn rights_consistent_through_perft_trees() {
+    assert_rights_consistent(&mut Game::from_fen(ROOKS).unwrap(), 2);
+    assert_rights_consistent(&mut Game::from_fen(KIWIPETE).unwrap(), 2);
+    assert_rights_consistent(&mut Game::from_fen(POSITION_5).unwrap(), 2);
+}
+
+#[test]
+fn rejected_moves_keep_rights() {
+    let fen = "4kr2/8/8/8/8/8/8/R3K2R w KQ - 0 1";
+    let mut game = Game::from_fen(fen).unwrap();
+
+    assert_eq!(
+        game.move_piece(square("e1"), square("g1")),
+        Err(MoveError::CastlingThroughCheck)
+    );
+    assert_eq!(
+        game.move_piece(square("e1"), square("f2")),
+        Err(MoveError::LeavesKingInCheck)
+    );
+    assert_eq!(
+        game.move_piece(square("h1"), square("g2")),
+        Err(MoveError::IllegalPieceMove)
+    );
+    assert!(game.move_uci("e1f1").is_err());
+    assert!(game.move_san("Kf1").is_err());
+    assert_eq!(game.castle(), [[true, true], [false, false]]);
+    assert_eq!(game.to_fen(), fen);
+}
@@ -0,0 +1,176 @@
+//! Chess960 start positions, castling with the king and rook on any file,
+//! and X-FEN and Shredder-FEN castling rights.
+
+use chess::{CHESS960_POSITIONS, Game, MoveError, PgnTags, PieceKind, START_FEN, parse_pgn};
+
+const CORNER_ROOKS: &str = "rk2r3/pppppppp/8/8/8/8/PPPPPPPP/RK2R3 w KQkq - 0 1";
+const INNER_ROOK: &str = "1r2k1rr/pppppppp/8/8/8/8/PPPPPPPP/1R2K1RR w GBgb - 0 1";
+
+fn assert_perft(fen: &str, counts: &[u64]) {
+    let mut game = Game::from_fen(fen).unwrap();
+    for (depth, &expected) in counts.iter().enumerate() {
+        assert_eq!(
+            game.perft(depth + 1),
+            expected,
+            "{} at depth {}",
+            fen,
+            depth + 1
+        );
+    }
+    assert_eq!(game.to_shredder_fen(), fen);
+}
+
+/// Plays `mov` in SAN and in long algebraic notation, checking that both
+/// reach the expected X-FEN.
+fn assert_after(fen: &str, san: &str, uci: &str, expected: &str) {
+    let mut game = Game::from_fen(fen).unwrap();
+    game.move_san(san).unwrap();
+    assert_eq!(game.to_fen(), expected);
+
+    let mut game = Game::from_fen(fen).unwrap();
+    game.move_uci(uci).unwrap();
+    assert_eq!(game.to_fen(), expected);
+}
+
+#[test]
+fn position_518_is_the_standard_start() {
+    let game = Game::from_960(518).unwrap();
+    assert_eq!(game.to_fen(), START_FEN);
+    assert!(game.is_chess960());
+    assert!(Game::from_960(CHESS960_POSITIONS).is_none());
+}
+
+#[test]
+fn scharnagl_numbering() {
+    let back_rank = |index| Game::from_960(index).unwrap().to_fen()[..8].to_string();
+    assert_eq!(back_rank(0), "bbqnnrkr");
+    assert_eq!(back_rank(1), "bqnbnrkr");
+    assert_eq!(back_rank(959), "rkrnnqbb");
+}
+
+#[test]
+fn every_start_position_is_distinct_and_valid() {
+    let mut seen = std::collections::HashSet::new();
+    for index in 0..CHESS960_POSITIONS {
+        let game = Game::from_960(index).unwrap();
+        let fen = game.to_fen();
+        assert!(seen.insert(fen.clone()), "{} repeats", fen);
+
+        let back: Vec<PieceKind> = game.board()[..8]
+            .iter()
+            .map(|piece| piece.unwrap().kind)
+            .collect();
+        let files = |kind| -> Vec<usize> { (0..8).filter(|&x| back[x] == kind).collect() };
+        let bishops = files(PieceKind::Bishop);
+        let rooks = files(PieceKind::Rook);
+        let king = files(PieceKind::King)[0];
+        assert_ne!(bishops[0] % 2, bishops[1] % 2, "{}", fen);
+        assert!(rooks[0] < king && king < rooks[1], "{}", fen);
+
+        assert_eq!(Game::from_fen(&fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn perft_positions() {
+    assert_perft(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        &[21, 528, 12189, 326672],
+    );
+    assert_perft(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        &[21, 807, 18002],
+    );
+    assert_perft(
+        "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+        &[20, 479, 10471],
+    );
+}
+
+#[test]
+fn castling_lands_on_the_standard_squares() {
+    assert_after(
+        CORNER_ROOKS,
+        "O-O-O",
+        "b1a1",
+        "rk2r3/pppppppp/8/8/8/8/PPPPPPPP/2KRR3 b kq - 1 1",
+    );
+    assert_after(
+        CORNER_ROOKS,
+        "O-O",
+        "b1e1",
+        "rk2r3/pppppppp/8/8/8/8/PPPPPPPP/R4RK1 b kq - 1 1",
+    );
+}
+
+#[test]
+fn castling_with_the_king_already_in_place() {
+    assert_after(
+        "rk5r/pppppppp/8/8/8/8/PPPPPPPP/R5KR w KQkq - 0 1",
+        "O-O",
+        "g1h1",
+        "rk5r/pppppppp/8/8/8/8/PPPPPPPP/R4RK1 b kq - 1 1",
+    );
+}
+
+#[test]
+fn castling_path_includes_the_king_destination() {
+    let fen = "1rn3kr/pppppppp/8/8/8/8/PPPPPPPP/1RN3KR w HBhb - 0 1";
+    let mut game = Game::from_fen(fen).unwrap();
+    assert_eq!(game.move_san("O-O-O"), Err(MoveError::BlockedPath));
+}
+
+#[test]
+fn inner_rook_keeps_its_file_letter() {
+    let game = Game::from_fen(INNER_ROOK).unwrap();
+    assert_eq!(
+        game.to_fen(),
+        "1r2k1rr/pppppppp/8/8/8/8/PPPPPPPP/1R2K1RR w GQgq - 0 1"
+    );
+    assert_eq!(game.to_shredder_fen(), INNER_ROOK);
+    assert_after(
+        INNER_ROOK,
+        "O-O",
+        "e1g1",
+        "1r2k1rr/pppppppp/8/8/8/8/PPPPPPPP/1R3RKR b gq - 1 1",
+    );
+}
+
+#[test]
+fn x_fen_and_shredder_fen_agree() {
+    let x_fen = Game::from_fen(CORNER_ROOKS).unwrap();
+    let shredder = Game::from_fen("rk2r3/pppppppp/8/8/8/8/PPPPPPPP/RK2R3 w EAea - 0 1").unwrap();
+    assert_eq!(x_fen.position_key(), shredder.position_key());
+    assert_eq!(x_fen.castling_rooks(), shredder.castling_rooks());
+    assert_eq!(shredder.to_fen(), CORNER_ROOKS);
+    assert_eq!(
+        x_fen.to_shredder_fen(),
+        "rk2r3/pppppppp/8/8/8/8/PPPPPPPP/RK2R3 w EAea - 0 1"
+    );
+}
+
+#[test]
+fn standard_games_keep_standard_notation() {
+    let game = Game::from_fen(START_FEN).unwrap();
+    assert!(!game.is_chess960());
+    assert_eq!(
+        game.to_shredder_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1"
+    );
+    assert!(Game::from_fen("rk2r3/pppppppp/8/8/8/8/PPPPPPPP/RK2R3 w C - 0 1").is_err());
+}
+
+#[test]
+fn pgn_round_trip() {
+    let mut game = Game::from_960(518).unwrap();
+    for san in ["e4", "e5", "Nf3", "Nc6", "Bc4", "Bc5", "O-O"] {
+        game.move_san(san).unwrap();
+    }
+    assert_eq!(game.moves().last().unwrap().to_string(), "e1h1");
+
+    let pgn = game.to_pgn(&PgnTags::default());
+    assert!(pgn.contains("[Variant \"Chess960\"]"));
+    let replayed = &parse_pgn(&pgn).unwrap()[0].game;
+    assert!(replayed.is_chess960());
+    assert_eq!(replayed.to_fen(), game.to_fen());
+}
@@ -0,0 +1,147 @@
+//! FEN round trips and the error for each kind of malformed or impossible
+//! position.
+
+use chess::{Game, START_FEN};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn assert_rejected(fen: &str, error: &str) {
+    match Game::from_fen(fen) {
+        Ok(_) => panic!("'{}' was accepted", fen),
+        Err(e) => assert_eq!(e, error, "{}", fen),
+    }
+}
+
+#[test]
+fn round_trip() {
+    for fen in [
+        START_FEN,
+        KIWIPETE,
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        "4k3/8/8/8/8/8/8/4K2R b K - 37 112",
+    ] {
+        assert_eq!(Game::from_fen(fen).unwrap().to_fen(), fen);
+    }
+}
+
+#[test]
+fn round_trip_after_moves() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    for san in ["e4", "c5", "Nf3", "d6", "Bb5+", "Bd7"] {
+        game.move_san(san).unwrap();
+        let fen = game.to_fen();
+        let reloaded = Game::from_fen(&fen).unwrap();
+        assert_eq!(reloaded.to_fen(), fen);
+        assert_eq!(reloaded.position_key(), game.position_key());
+    }
+}
+
+#[test]
+fn rejects_malformed_fields() {
+    assert_rejected(
+        "8/8/8/8/8/8/8/8 w - -",
+        "FEN must have 6 fields, found 4 in '8/8/8/8/8/8/8/8 w - -'",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/4K3 w - - 0 1",
+        "FEN board must have 8 ranks, found 7",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2 w - - 0 1",
+        "Rank 1 has 7 squares, expected 8",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K4 w - - 0 1",
+        "Rank 1 has more than 8 squares",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K3/ w - - 0 1",
+        "FEN board must have 8 ranks, found 9",
+    );
+    assert_rejected(
+        "4k3/8/8/9/8/8/8/4K3 w - - 0 1",
+        "Invalid empty square count '9' on rank 5",
+    );
+    assert_rejected(
+        "4k3/8/8/8/3X4/8/8/4K3 w - - 0 1",
+        "Invalid piece 'X' on rank 4",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K3 x - - 0 1",
+        "Invalid side to move 'x', expected 'w' or 'b'",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K3 w - - x 1",
+        "Invalid halfmove clock 'x'",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 0",
+        "Invalid fullmove number '0'",
+    );
+}
+
+#[test]
+fn rejects_impossible_positions() {
+    assert_rejected(
+        "8/8/8/8/8/8/8/4K3 w - - 0 1",
+        "Black must have exactly one king, found 0",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/3KK3 w - - 0 1",
+        "White must have exactly one king, found 2",
+    );
+    assert_rejected(
+        "4k2p/8/8/8/8/8/8/4K3 w - - 0 1",
+        "Pawns cannot stand on rank 8",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/P3K3 w - - 0 1",
+        "Pawns cannot stand on rank 1",
+    );
+    assert_rejected(
+        "4k2R/8/8/8/8/8/8/4K3 w - - 0 1",
+        "Black is in check but it is White to move",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2r b - - 0 1",
+        "White is in check but it is Black to move",
+    );
+}
+
+#[test]
+fn rejects_bad_castling_rights() {
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2R w X - 0 1",
+        "Invalid castling right 'X'",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2R w KK - 0 1",
+        "Duplicate castling right 'K'",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K3 w K - 0 1",
+        "Castling right 'K' requires a rook on that side of the king",
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/4K3/7R w K - 0 1",
+        "Castling right 'K' requires the king on its back rank",
+    );
+}
+
+#[test]
+fn rejects_bad_en_passant_squares() {
+    assert_rejected(
+        "4k3/8/8/4pP2/8/8/8/4K3 w - e9 0 1",
+        "Invalid en passant square 'e9'",
+    );
+    assert_rejected(
+        "4k3/8/8/4pP2/8/8/8/4K3 w - e3 0 1",
+        "En passant square 'e3' is not on rank 6",
+    );
+    assert_rejected(
+        "4k3/8/8/5P2/8/8/8/4K3 w - e6 0 1",
+        "En passant square 'e6' has no pawn that just moved two squares",
+    );
+}
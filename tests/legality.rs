@@ -0,0 +1,178 @@
+//! The error `is_legal` picks for each kind of illegal move, and that a
+//! rejected move leaves the game untouched.
+
+use chess::{Game, Move, MoveError, START_FEN, Square};
+
+fn square(name: &str) -> Square {
+    name.parse().unwrap()
+}
+
+/// A move from `from` to `to` with no flags set.
+fn quiet(from: &str, to: &str) -> Move {
+    Move {
+        from: square(from),
+        to: square(to),
+        promotion: None,
+        capture: false,
+        castle: false,
+        en_passant: false,
+        double_push: false,
+    }
+}
+
+fn legality(fen: &str, mov: Move) -> Result<(), MoveError> {
+    Game::from_fen(fen).unwrap().is_legal(mov)
+}
+
+/// Checks that `make_move` rejects `mov` with `error` and leaves the
+/// position and its key as they were.
+fn assert_rejected(fen: &str, mov: Move, error: MoveError) {
+    let mut game = Game::from_fen(fen).unwrap();
+    let key = game.position_key();
+    assert_eq!(game.make_move(mov), Err(error), "{}", mov);
+    assert_eq!(game.to_fen(), fen);
+    assert_eq!(game.position_key(), key);
+    assert!(game.moves().is_empty());
+}
+
+#[test]
+fn generated_moves_are_legal() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    let moves: Vec<Move> = game.legal_moves().collect();
+    for mov in moves {
+        assert_eq!(game.is_legal(mov), Ok(()), "{}", mov);
+    }
+    let mov = game.parse_san("e4").unwrap();
+    game.make_move(mov).unwrap();
+    assert_eq!(
+        game.is_legal(mov),
+        Err(MoveError::NoPieceOnSquare(square("e2")))
+    );
+}
+
+#[test]
+fn piece_on_the_from_square() {
+    assert_eq!(
+        legality(START_FEN, quiet("e4", "e5")),
+        Err(MoveError::NoPieceOnSquare(square("e4")))
+    );
+    assert_eq!(
+        legality(START_FEN, quiet("g8", "f6")),
+        Err(MoveError::WrongSide)
+    );
+}
+
+#[test]
+fn blocked_path() {
+    assert_eq!(
+        legality(START_FEN, quiet("a1", "a3")),
+        Err(MoveError::BlockedPath)
+    );
+    assert_eq!(
+        legality(START_FEN, quiet("c1", "e3")),
+        Err(MoveError::BlockedPath)
+    );
+}
+
+#[test]
+fn pinned_piece() {
+    let fen = "4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1";
+    assert_eq!(
+        legality(fen, quiet("e2", "c3")),
+        Err(MoveError::LeavesKingInCheck)
+    );
+    assert_eq!(legality(fen, quiet("e1", "d1")), Ok(()));
+}
+
+#[test]
+fn flags_must_match_the_move() {
+    let double_push = Move {
+        double_push: true,
+        ..quiet("e2", "e4")
+    };
+    assert_eq!(legality(START_FEN, double_push), Ok(()));
+    assert_eq!(
+        legality(START_FEN, quiet("e2", "e4")),
+        Err(MoveError::InconsistentMove)
+    );
+    assert_eq!(
+        legality(
+            START_FEN,
+            Move {
+                capture: true,
+                ..quiet("g1", "f3")
+            }
+        ),
+        Err(MoveError::InconsistentMove)
+    );
+    assert_eq!(
+        legality(
+            START_FEN,
+            Move {
+                castle: true,
+                ..quiet("g1", "f3")
+            }
+        ),
+        Err(MoveError::InconsistentMove)
+    );
+}
+
+#[test]
+fn rejected_en_passant_leaves_the_game_unchanged() {
+    let en_passant = Move {
+        capture: true,
+        en_passant: true,
+        ..quiet("e5", "d6")
+    };
+    // Taking en passant would uncover the rook's attack on the king.
+    assert_rejected(
+        "8/8/8/K2pP2r/8/8/8/4k3 w - d6 0 1",
+        en_passant,
+        MoveError::LeavesKingInCheck,
+    );
+    // The capture without its flags.
+    assert_rejected(
+        "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1",
+        quiet("e5", "d6"),
+        MoveError::InconsistentMove,
+    );
+    // The pawn did not just move two squares.
+    assert_rejected(
+        "4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1",
+        en_passant,
+        MoveError::IllegalPieceMove,
+    );
+}
+
+#[test]
+fn rejected_castling_leaves_the_game_unchanged() {
+    let castle = Move {
+        castle: true,
+        ..quiet("e1", "g1")
+    };
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2R w - - 0 1",
+        castle,
+        MoveError::CastlingRightsLost,
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/5r2/4K2R w K - 0 1",
+        castle,
+        MoveError::CastlingThroughCheck,
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/4r3/4K2R w K - 0 1",
+        castle,
+        MoveError::CastlingOutOfCheck,
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K1NR w K - 0 1",
+        castle,
+        MoveError::BlockedPath,
+    );
+    assert_rejected(
+        "4k3/8/8/8/8/8/8/4K2R w K - 0 1",
+        quiet("e1", "g1"),
+        MoveError::InconsistentMove,
+    );
+}
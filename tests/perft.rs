@@ -0,0 +1,109 @@
+//! Node counts from the Chess Programming Wiki's perft results page, plus
+//! single-depth counts for positions built around generator edge cases.
+
+use chess::{Game, START_FEN};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_4_MIRRORED: &str =
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+fn assert_perft(fen: &str, counts: &[u64]) {
+    let mut game = Game::from_fen(fen).unwrap();
+    for (depth, &expected) in counts.iter().enumerate() {
+        assert_eq!(
+            game.perft(depth + 1),
+            expected,
+            "{} at depth {}",
+            fen,
+            depth + 1
+        );
+    }
+    assert_eq!(game.to_fen(), fen);
+}
+
+fn assert_perft_at(fen: &str, depth: usize, expected: u64) {
+    let mut game = Game::from_fen(fen).unwrap();
+    assert_eq!(game.perft(depth), expected, "{} at depth {}", fen, depth);
+}
+
+#[test]
+fn start_position() {
+    assert_perft(START_FEN, &[20, 400, 8902, 197281]);
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+}
+
+#[test]
+fn position_4_mirrored() {
+    assert_perft(POSITION_4_MIRRORED, &[6, 264, 9467]);
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
+
+#[test]
+fn position_6() {
+    assert_perft(POSITION_6, &[46, 2079, 89890]);
+}
+
+#[test]
+fn divide_sums_to_perft() {
+    let mut game = Game::from_fen(KIWIPETE).unwrap();
+    let divide = game.perft_divide(2);
+    assert_eq!(divide.len(), 48);
+    assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+}
+
+#[test]
+fn en_passant_exposing_the_king() {
+    assert_perft_at("3k4/3p4/8/K1P4r/8/8/8/8 b - - 0 1", 6, 1134888);
+}
+
+#[test]
+fn en_passant_giving_check() {
+    assert_perft_at("8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1", 6, 1440467);
+}
+
+#[test]
+fn castling_giving_check() {
+    assert_perft_at("5k2/8/8/8/8/8/8/4K2R w K - 0 1", 6, 661072);
+}
+
+#[test]
+fn castling_through_attacked_squares() {
+    assert_perft_at("r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq - 0 1", 4, 1720476);
+}
+
+#[test]
+fn promotion_out_of_check() {
+    assert_perft_at("2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1", 6, 3821001);
+}
+
+#[test]
+fn discovered_check() {
+    assert_perft_at("8/8/1P2K3/8/2n5/1q6/8/5k2 b - - 0 1", 5, 1004658);
+}
+
+#[test]
+fn stalemate_and_checkmate() {
+    assert_perft_at("8/k1P5/8/1K6/8/8/8/8 w - - 0 1", 7, 567584);
+}
@@ -0,0 +1,181 @@
+//! Reading PGN documents: comments, escapes, NAGs, variations, several
+//! games per document, tag values and the errors reported for bad input.
+
+use chess::{Game, PgnTags, START_FEN, parse_pgn};
+
+const RUY_LOPEZ: &str = "r1bqkbnr/1ppp1ppp/p1n5/4p3/B3P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 1 4";
+
+fn error(pgn: &str) -> String {
+    match parse_pgn(pgn) {
+        Ok(_) => panic!("'{}' was accepted", pgn),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn tags_and_result() {
+    let pgn = "[Event \"Casual\"]\n[White \"Anderssen\"]\n\n1. e4 e5 2. Nf3 Nc6 1-0\n";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].tag("Event"), Some("Casual"));
+    assert_eq!(games[0].tag("White"), Some("Anderssen"));
+    assert_eq!(games[0].tag("Black"), None);
+    assert_eq!(games[0].result, "1-0");
+    assert_eq!(games[0].game.moves().len(), 4);
+}
+
+#[test]
+fn tag_escapes() {
+    let pgn = r#"[Event "The \"Immortal\" Game"] [Site "C:\\chess"] *"#;
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].tag("Event"), Some("The \"Immortal\" Game"));
+    assert_eq!(games[0].tag("Site"), Some("C:\\chess"));
+    assert_eq!(games[0].result, "*");
+}
+
+#[test]
+fn comments_escapes_and_nags() {
+    let pgn = "%escaped line with 1. d4\n\
+               1. e4 {best by test} e5 $1 ; rest of the line 2. d4\n\
+               2. Nf3 $14 Nc6 3. Bb5 {a {brace} a6 4. Ba4 *";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].game.to_fen(), RUY_LOPEZ);
+}
+
+#[test]
+fn percent_only_escapes_at_line_start() {
+    assert_eq!(
+        error("1. e4 %not an escape\n*"),
+        "Game 1, ply 2 (1... %not): Invalid move: bad destination square in '%not'"
+    );
+}
+
+#[test]
+fn move_numbers_with_any_number_of_periods() {
+    let pgn = "1 e4 e5 2.Nf3 2...Nc6 3 Bb5 3.. a6 4.Ba4 *";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].game.to_fen(), RUY_LOPEZ);
+    assert_eq!(parse_pgn("1 e4 e5 *").unwrap()[0].game.moves().len(), 2);
+}
+
+#[test]
+fn nested_variations_are_skipped() {
+    let pgn = "1. e4 e5 (1... c5 2. Nf3 (2. c3 d5) 2... d6) 2. Nf3 Nc6 \
+               (2... d6 3. d4 (3. Bc4)) 3. Bb5 a6 4. Ba4 *";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].game.to_fen(), RUY_LOPEZ);
+}
+
+#[test]
+fn several_games() {
+    let pgn = "[Event \"First\"]\n\n1. e4 e5 1/2-1/2\n\n\
+               [Event \"Second\"]\n\n1. d4 d5 2. c4 0-1\n\n\
+               [Event \"Third\"]\n\n1. Nf3\n";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games.len(), 3);
+    let summary: Vec<(&str, &str, usize)> = games
+        .iter()
+        .map(|g| {
+            (
+                g.tag("Event").unwrap(),
+                g.result.as_str(),
+                g.game.moves().len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("First", "1/2-1/2", 2),
+            ("Second", "0-1", 3),
+            ("Third", "*", 1)
+        ]
+    );
+}
+
+#[test]
+fn fen_tag_sets_the_start() {
+    let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K2R b K - 0 30\"]\n\n30... Kd7 31. O-O *";
+    let games = parse_pgn(pgn).unwrap();
+    assert_eq!(games[0].game.to_fen(), "8/3k4/8/8/8/8/8/5RK1 b - - 2 31");
+}
+
+#[test]
+fn illegal_move_names_game_and_ply() {
+    assert_eq!(
+        error("1. e4 e5 *\n\n1. e4 e5 2. Ke3 *"),
+        "Game 2, ply 3 (2. Ke3): Illegal move 'Ke3'"
+    );
+    assert_eq!(
+        error("1. e4 e5 2. Nf3 Nf3 *"),
+        "Game 1, ply 4 (2... Nf3): Illegal move 'Nf3'"
+    );
+}
+
+#[test]
+fn ambiguous_move_names_game_and_ply() {
+    assert_eq!(
+        error("1. Nc3 a6 2. Nf3 a5 3. Nd4 a4 4. Nb5 *"),
+        "Game 1, ply 7 (4. Nb5): Ambiguous move 'Nb5'"
+    );
+}
+
+#[test]
+fn malformed_documents() {
+    assert_eq!(error("1. e4 {never closed"), "Unterminated comment");
+    assert_eq!(error("1. e4 (1. d4 *"), "Unterminated variation");
+    assert_eq!(error("1. e4 ) *"), "Unmatched ')' closing a variation");
+    assert_eq!(error("[Event Casual] *"), "Tag 'Event' has no quoted value");
+    assert_eq!(
+        error("[Event \"Casual"),
+        "Unterminated value for tag 'Event'"
+    );
+    assert_eq!(
+        error("[Event \"Casual\" *"),
+        "Tag 'Event' is missing its closing ']'"
+    );
+    assert_eq!(
+        error("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"] *"),
+        "Game 1: invalid FEN tag: White must have exactly one king, found 0"
+    );
+}
+
+#[test]
+fn round_trip_through_to_pgn() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    for san in [
+        "e4", "e5", "Nf3", "Nc6", "Bc4", "Bc5", "b4", "Bxb4", "c3", "Ba5", "d4", "exd4", "O-O",
+        "d3", "Qb3", "Qf6", "e5", "Qg6", "Re1", "Nge7", "Ba3", "b5", "Qxb5", "Rb8",
+    ] {
+        game.move_san(san).unwrap();
+    }
+    let tags = PgnTags {
+        event: "Evergreen \"Game\"".to_string(),
+        white: "Anderssen".to_string(),
+        black: "Dufresne".to_string(),
+        ..PgnTags::default()
+    };
+    let pgn = game.to_pgn(&tags);
+
+    let games = parse_pgn(&pgn).unwrap();
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].tag("Event"), Some("Evergreen \"Game\""));
+    assert_eq!(games[0].tag("Result"), Some("*"));
+    assert_eq!(games[0].game.moves(), game.moves());
+    assert_eq!(games[0].game.to_fen(), game.to_fen());
+    assert_eq!(games[0].game.to_pgn(&tags), pgn);
+}
+
+#[test]
+fn round_trip_from_a_fen_start() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/8/4K2R b K - 0 30").unwrap();
+    for san in ["Kd7", "O-O", "Kc6", "Rf6+"] {
+        game.move_san(san).unwrap();
+    }
+    let pgn = game.to_pgn(&PgnTags::default());
+    assert!(pgn.contains("[FEN \"4k3/8/8/8/8/8/8/4K2R b K - 0 30\"]"));
+    assert!(pgn.contains("30... Kd7 31. O-O Kc6 32. Rf6+ *"));
+
+    let replayed = Game::from_pgn(&pgn).unwrap();
+    assert_eq!(replayed.to_fen(), game.to_fen());
+}
@@ -0,0 +1,50 @@
+//! Check and mate suffixes in SAN, including moves that also end the game
+//! by a draw rule.
+
+use chess::{Game, START_FEN};
+
+/// Plays `moves` in SAN from `fen` and returns how `next` is written.
+fn san_after(fen: &str, moves: &[&str], next: &str) -> String {
+    let mut game = Game::from_fen(fen).unwrap();
+    for san in moves {
+        game.move_san(san).unwrap();
+    }
+    let mov = game.parse_san(next).unwrap();
+    game.san(mov)
+}
+
+#[test]
+fn check_and_mate() {
+    assert_eq!(san_after(START_FEN, &["f3", "e5", "g4"], "Qh4"), "Qh4#");
+    assert_eq!(san_after(START_FEN, &["e4", "f5"], "Qh5"), "Qh5+");
+    assert_eq!(san_after(START_FEN, &[], "e4"), "e4");
+}
+
+#[test]
+fn check_completing_a_threefold_repetition() {
+    let moves = ["Rg1", "Kh7", "Rh1", "Kg8", "Rg1", "Kh7", "Rh1", "Kg8"];
+    assert_eq!(
+        san_after("6k1/8/8/8/8/8/8/K6R w - - 0 1", &moves, "Rg1"),
+        "Rg1+"
+    );
+}
+
+#[test]
+fn check_reaching_the_fifty_move_rule() {
+    assert_eq!(
+        san_after("7k/8/8/8/8/8/8/K5R1 w - - 99 80", &[], "Rh1"),
+        "Rh1+"
+    );
+    assert_eq!(
+        san_after("7k/8/6K1/8/8/8/8/R7 w - - 99 80", &[], "Ra8"),
+        "Ra8#"
+    );
+}
+
+#[test]
+fn check_in_a_dead_position() {
+    assert_eq!(
+        san_after("7k/8/8/8/8/8/8/2B1K3 w - - 0 1", &[], "Bb2"),
+        "Bb2+"
+    );
+}
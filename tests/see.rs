@@ -0,0 +1,78 @@
+//! Static exchange evaluation on known positions, with a pawn worth 10, a
+//! knight 30, a rook 50 and a queen 90.
+
+use chess::{Game, Move, Square};
+
+fn see(fen: &str, san: &str) -> isize {
+    let game = Game::from_fen(fen).unwrap();
+    let mov = game.parse_san(san).unwrap();
+    game.see(mov)
+}
+
+#[test]
+fn undefended_pawn() {
+    assert_eq!(
+        see("1k1r4/1pp4p/p7/4p3/8/P5P1/PPP4P/2K1R3 w - - 0 1", "Rxe5"),
+        10
+    );
+}
+
+#[test]
+fn knight_for_pawn_with_x_rays() {
+    assert_eq!(
+        see(
+            "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/PPP1Q2P/2K1R3 w - - 0 1",
+            "Nxe5"
+        ),
+        10 - 30
+    );
+}
+
+#[test]
+fn defended_pawn_taken_by_a_pawn() {
+    assert_eq!(see("4k3/8/3p4/4p3/3P4/8/8/4K3 w - - 0 1", "dxe5"), 0);
+}
+
+#[test]
+fn stopping_early() {
+    // Black keeps the queen rather than win the rook, as Qxd5 would lose
+    // it to the queen behind.
+    assert_eq!(see("3qk3/8/8/3p4/8/8/3R4/3QK3 w - - 0 1", "Rxd5"), 10);
+    // Here white does continue, winning the pawn back after cxd5.
+    assert_eq!(
+        see("4k3/8/2p5/3p4/8/8/3R4/3QK3 w - - 0 1", "Rxd5"),
+        10 - 50 + 10
+    );
+}
+
+#[test]
+fn en_passant() {
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 10);
+    assert_eq!(see("4k3/2p5/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 0);
+}
+
+#[test]
+fn promotion() {
+    assert_eq!(see("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e8=Q"), 90 - 10);
+    assert_eq!(see("8/4Pk2/8/8/8/8/8/4K3 w - - 0 1", "e8=Q+"), -10);
+    assert_eq!(
+        see("3rk3/4P3/8/8/8/8/8/4K3 w - - 0 1", "exd8=Q+"),
+        50 + 90 - 10 - 90
+    );
+}
+
+#[test]
+fn empty_from_square() {
+    let game = Game::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let square = |name: &str| name.parse::<Square>().unwrap();
+    let mov = Move {
+        from: square("d4"),
+        to: square("d5"),
+        promotion: None,
+        capture: false,
+        castle: false,
+        en_passant: false,
+        double_push: false,
+    };
+    assert_eq!(game.see(mov), 0);
+}
@@ -0,0 +1,220 @@
+//! How `check_game_end` reports dead positions, repetitions and the move
+//! count rules.
+
+use chess::{Game, START_FEN, State};
+
+/// Plays `moves` in SAN from `fen` and describes the resulting state.
+fn state_after(fen: &str, moves: &[&str]) -> String {
+    let mut game = Game::from_fen(fen).unwrap();
+    for san in moves {
+        game.move_san(san).unwrap();
+    }
+    game.check_game_end().symbol()
+}
+
+fn state(fen: &str) -> String {
+    state_after(fen, &[])
+}
+
+#[test]
+fn lone_minor_piece_is_dead() {
+    let dead = State::DeadPosition.symbol();
+    assert_eq!(state("4k3/8/8/8/8/8/8/4K3 w - - 0 1"), dead);
+    assert_eq!(state("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1"), dead);
+    assert_eq!(state("4k3/8/8/8/8/8/8/1N2K3 b - - 0 1"), dead);
+    assert_eq!(state("4k3/8/3n4/8/8/8/8/4K3 w - - 0 1"), dead);
+}
+
+#[test]
+fn bishops_on_one_colour_are_dead() {
+    let dead = State::DeadPosition.symbol();
+    assert_eq!(state("4k3/8/7b/8/8/8/8/2B1K3 w - - 0 1"), dead);
+    assert_eq!(state("4k3/8/8/8/8/B7/8/2B1K3 w - - 0 1"), dead);
+    assert_eq!(state("4k3/8/1b5b/8/8/B7/8/2B1K3 b - - 0 1"), dead);
+}
+
+#[test]
+fn material_that_can_still_mate_continues() {
+    let continues = State::Continue.symbol();
+    assert_eq!(state("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1"), continues);
+    assert_eq!(state("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1"), continues);
+    assert_eq!(state("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1"), continues);
+    assert_eq!(state("4k3/8/8/8/8/8/8/2B1KN2 w - - 0 1"), continues);
+    assert_eq!(state("4k3/8/8/8/8/8/8/4K2R w - - 0 1"), continues);
+    assert_eq!(state("4k3/8/8/8/8/8/P7/4K3 w - - 0 1"), continues);
+}
+
+#[test]
+fn capture_leaving_bare_kings_is_dead() {
+    assert_eq!(
+        state_after("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1", &["Kxd2"]),
+        State::DeadPosition.symbol()
+    );
+    assert_eq!(
+        state_after("4k3/8/8/8/8/8/3r4/2B1K3 w - - 0 1", &["Bxd2"]),
+        State::DeadPosition.symbol()
+    );
+}
+
+const SHUFFLE: [&str; 4] = ["Nf3", "Nf6", "Ng1", "Ng8"];
+
+/// The knight shuffle played `times` times from the start position.
+fn shuffles(times: usize) -> Vec<&'static str> {
+    SHUFFLE.repeat(times)
+}
+
+#[test]
+fn threefold_repetition() {
+    assert_eq!(
+        state_after(START_FEN, &shuffles(1)),
+        State::Continue.symbol()
+    );
+    assert_eq!(
+        state_after(START_FEN, &shuffles(2)),
+        State::ThreefoldRepetition.symbol()
+    );
+    // The position after 1. Nf3 has now also occurred three times.
+    let mut moves = shuffles(2);
+    moves.push("Nf3");
+    assert_eq!(
+        state_after(START_FEN, &moves),
+        State::ThreefoldRepetition.symbol()
+    );
+}
+
+#[test]
+fn fivefold_repetition() {
+    assert_eq!(
+        state_after(START_FEN, &shuffles(3)),
+        State::ThreefoldRepetition.symbol()
+    );
+    assert_eq!(
+        state_after(START_FEN, &shuffles(4)),
+        State::FivefoldRepetition.symbol()
+    );
+}
+
+#[test]
+fn threefold_repetition_must_be_claimed() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    for san in shuffles(2) {
+        game.move_san(san).unwrap();
+    }
+    assert!(!game.is_game_over());
+    assert_eq!(game.result(), "*");
+
+    assert_eq!(
+        game.claim_draw().map(|draw| draw.symbol()),
+        Some(State::ThreefoldRepetition.symbol())
+    );
+    assert!(game.is_game_over());
+    assert_eq!(game.result(), "1/2-1/2");
+}
+
+#[test]
+fn check_is_reported_ahead_of_a_repetition() {
+    let mut game = Game::from_fen("6k1/8/8/8/8/8/8/K6R w - - 0 1").unwrap();
+    for san in ["Rg1", "Kh7", "Rh1", "Kg8"].repeat(2) {
+        game.move_san(san).unwrap();
+    }
+    game.move_san("Rg1").unwrap();
+    assert_eq!(game.check_game_end().symbol(), State::BlackCheck.symbol());
+    assert_eq!(
+        game.claimable_draw().map(|draw| draw.symbol()),
+        Some(State::ThreefoldRepetition.symbol())
+    );
+}
+
+#[test]
+fn repetition_needs_the_same_side_to_move() {
+    // The rook cycles through three squares and the king through two, so
+    // the starting placement comes back once with black to move and once
+    // with white to move.
+    let moves = [
+        "Rh2", "Kd8", "Rh3", "Ke8", "Rh1", "Kd8", "Rh2", "Ke8", "Rh3", "Kd8", "Rh1", "Ke8",
+    ];
+    assert_eq!(
+        state_after("4k3/8/8/8/8/8/8/4K2R w - - 0 1", &moves),
+        State::Continue.symbol()
+    );
+}
+
+#[test]
+fn lost_castling_rights_change_the_position() {
+    // After Kf1 and back the pieces stand as at the start, but white can no
+    // longer castle, so the start does not count towards the repetition.
+    let fen = "4k3/8/8/8/8/8/8/4K2R w K - 0 1";
+    let round = ["Kf1", "Ke7", "Ke1", "Ke8"];
+    assert_eq!(state_after(fen, &round.repeat(2)), State::Continue.symbol());
+    assert_eq!(
+        state_after(fen, &round.repeat(3)),
+        State::ThreefoldRepetition.symbol()
+    );
+}
+
+#[test]
+fn fifty_move_rule() {
+    let fen = "4k3/8/8/8/8/8/P7/R3K3 w - - 99 80";
+    assert_eq!(state(fen), State::Continue.symbol());
+    assert_eq!(state_after(fen, &["Rb1"]), State::FiftyMoveRule.symbol());
+    // A pawn move resets the count.
+    assert_eq!(state_after(fen, &["a4"]), State::Continue.symbol());
+}
+
+#[test]
+fn seventy_five_move_rule() {
+    let fen = "4k3/8/8/8/8/8/P7/R3K3 w - - 149 105";
+    assert_eq!(state(fen), State::FiftyMoveRule.symbol());
+    assert_eq!(
+        state_after(fen, &["Rb1"]),
+        State::SeventyFiveMoveRule.symbol()
+    );
+}
+
+#[test]
+fn capture_resets_the_move_count() {
+    assert_eq!(
+        state_after("4k3/8/8/8/8/8/r7/R3K3 w - - 99 80", &["Rxa2"]),
+        State::Continue.symbol()
+    );
+}
+
+#[test]
+fn checkmate_takes_priority_over_the_move_count() {
+    assert_eq!(
+        state_after("7k/8/6K1/8/8/8/8/R7 w - - 99 80", &["Ra8"]),
+        State::BlackCheckmate.symbol()
+    );
+    assert_eq!(
+        state_after("7k/8/6K1/8/8/8/8/R7 w - - 149 105", &["Ra8"]),
+        State::BlackCheckmate.symbol()
+    );
+}
+
+#[test]
+fn fifty_move_draw_must_be_claimed() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/P7/R3K3 w - - 99 80").unwrap();
+    assert!(game.claim_draw().is_none());
+    game.move_san("Rb1").unwrap();
+    assert!(!game.is_game_over());
+    assert_eq!(game.result(), "*");
+    assert_eq!(
+        game.claimable_draw().map(|draw| draw.symbol()),
+        Some(State::FiftyMoveRule.symbol())
+    );
+
+    assert_eq!(
+        game.claim_draw().map(|draw| draw.symbol()),
+        Some(State::FiftyMoveRule.symbol())
+    );
+    assert!(game.is_game_over());
+    assert_eq!(game.result(), "1/2-1/2");
+}
+
+#[test]
+fn seventy_five_move_draw_needs_no_claim() {
+    let mut game = Game::from_fen("4k3/8/8/8/8/8/P7/R3K3 w - - 149 105").unwrap();
+    game.move_san("Rb1").unwrap();
+    assert!(game.is_game_over());
+    assert_eq!(game.result(), "1/2-1/2");
+}
@@ -0,0 +1,70 @@
+//! Long algebraic moves and their promotion suffixes.
+
+use chess::{Game, MoveError, ParseMoveError, PieceKind, START_FEN, Square};
+
+const PROMOTION: &str = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+const ROOKS: &str = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+#[test]
+fn promotion_suffix_selects_the_piece() {
+    let mut game = Game::from_fen(PROMOTION).unwrap();
+    game.move_uci("a7a8n").unwrap();
+    assert_eq!(game.to_fen(), "N3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    assert_eq!(game.promotion(), PieceKind::Queen);
+}
+
+#[test]
+fn missing_suffix_uses_the_selected_promotion() {
+    let mut game = Game::from_fen(PROMOTION).unwrap();
+    game.set_promotion(PieceKind::Rook).unwrap();
+    game.move_uci("a7a8").unwrap();
+    assert_eq!(game.to_fen(), "R3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn suffix_on_a_move_that_does_not_promote() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    assert_eq!(
+        game.move_uci("g1f3q"),
+        Err(MoveError::InvalidPromotion(PieceKind::Queen))
+    );
+    assert_eq!(
+        game.move_uci("e2e4n"),
+        Err(MoveError::InvalidPromotion(PieceKind::Knight))
+    );
+    assert_eq!(game.to_fen(), START_FEN);
+}
+
+#[test]
+fn promotion_letters() {
+    assert_eq!(
+        Game::parse_move("e7e8N").map(|(_, p)| p),
+        Ok(Some(PieceKind::Knight))
+    );
+    assert_eq!(
+        Game::parse_move("e7e8k"),
+        Err(ParseMoveError::InvalidPromotion('k'))
+    );
+    let mut game = Game::from_fen(PROMOTION).unwrap();
+    assert_eq!(
+        game.set_promotion(PieceKind::King),
+        Err(MoveError::InvalidPromotion(PieceKind::King))
+    );
+    assert_eq!(game.promotion(), PieceKind::Queen);
+}
+
+#[test]
+fn king_takes_rook_is_not_castling_in_standard_chess() {
+    let mut game = Game::from_fen(ROOKS).unwrap();
+    assert_eq!(game.move_uci("e1h1"), Err(MoveError::CaptureOwnPiece));
+    assert_eq!(game.move_uci("e1a1"), Err(MoveError::CaptureOwnPiece));
+    let square = |name: &str| name.parse::<Square>().unwrap();
+    assert_eq!(
+        game.move_piece(square("e1"), square("h1")),
+        Err(MoveError::CaptureOwnPiece)
+    );
+    assert_eq!(game.to_fen(), ROOKS);
+
+    game.move_uci("e1g1").unwrap();
+    assert_eq!(game.to_fen(), "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1");
+}
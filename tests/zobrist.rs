@@ -0,0 +1,81 @@
+//! Position keys checked against the test positions published with the
+//! Polyglot book format.
+
+use chess::{Game, START_FEN};
+
+/// Moves from the start position, the FEN they reach and its Polyglot key.
+const POSITIONS: [(&str, &str, u64); 9] = [
+    ("", START_FEN, 0x463b96181691fc9c),
+    (
+        "e2e4",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+        0x823c9b50fd114196,
+    ),
+    (
+        "e2e4 d7d5",
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+        0x0756b94461c50fb0,
+    ),
+    (
+        "e2e4 d7d5 e4e5",
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2",
+        0x662fafb965db29d4,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        0x22a48b5a8e47ff78,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5 e1e2",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 1 3",
+        0x652a607ca3f242c1,
+    ),
+    (
+        "e2e4 d7d5 e4e5 f7f5 e1e2 e8f7",
+        "rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 2 4",
+        0x00fdd303c946bdd9,
+    ),
+    (
+        "a2a4 b7b5 h2h4 b5b4 c2c4",
+        "rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3",
+        0x3c8123ea7b067637,
+    ),
+    (
+        "a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3",
+        "rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 1 4",
+        0x5c3f9b829b279560,
+    ),
+];
+
+#[test]
+fn keys_match_polyglot_after_moves() {
+    for (moves, fen, key) in POSITIONS {
+        let mut game = Game::from_fen(START_FEN).unwrap();
+        for mov in moves.split_whitespace() {
+            game.move_uci(mov).unwrap();
+        }
+        assert_eq!(game.to_fen(), fen);
+        assert_eq!(game.position_key(), key, "after '{}'", moves);
+    }
+}
+
+#[test]
+fn keys_match_polyglot_from_fen() {
+    for (moves, fen, key) in POSITIONS {
+        let game = Game::from_fen(fen).unwrap();
+        assert_eq!(game.position_key(), key, "after '{}'", moves);
+    }
+}
+
+#[test]
+fn takeback_restores_the_key() {
+    let mut game = Game::from_fen(START_FEN).unwrap();
+    for mov in ["e2e4", "d7d5", "e4e5", "f7f5"] {
+        game.move_uci(mov).unwrap();
+    }
+    for (_, _, key) in POSITIONS[..4].iter().rev() {
+        game.takeback().unwrap();
+        assert_eq!(game.position_key(), *key);
+    }
+}